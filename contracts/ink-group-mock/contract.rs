@@ -0,0 +1,109 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+pub use self::ink_group_mock::{InkGroupMock, InkGroupMockRef};
+
+/// Balance reader of a PSP22 token
+#[ink::trait_definition]
+pub trait PSP22 {
+    /// Return the balance of `owner`
+    #[ink(message)]
+    fn balance_of(&self, owner: ink::primitives::AccountId) -> u128;
+}
+
+#[ink::contract]
+mod ink_group_mock {
+    use ink::prelude::vec::Vec;
+    use ink::storage::Mapping;
+    use ink_group::{InkGroupClient, InkGroupError};
+
+    #[ink(storage)]
+    #[derive(Default)]
+    pub struct InkGroupMock {
+        /// token balances
+        balances: Mapping<AccountId, Balance>,
+        /// removed members notified, oldest first
+        removed: Vec<(AccountId, u64)>,
+        /// admin changes notified (old admin, new admin), oldest first
+        admin_changes: Vec<(AccountId, AccountId)>,
+        /// trap on the removal notifications
+        reject: bool,
+    }
+
+    impl InkGroupMock {
+        #[ink(constructor)]
+        /// Construct the mock without balances and accepting the notifications
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        #[ink(message)]
+        /// Set the token balance of `owner`
+        pub fn set_balance(&mut self, owner: AccountId, balance: Balance) {
+            self.balances.insert(owner, &balance);
+        }
+
+        #[ink(message)]
+        /// Trap on the next removal notifications if `reject`
+        pub fn set_reject(&mut self, reject: bool) {
+            self.reject = reject;
+        }
+
+        #[ink(message)]
+        /// Record a member removed from a group, trapping if the removals are rejected
+        pub fn notify_removed(&mut self, member: AccountId, weight: u64) {
+            assert!(!self.reject, "removal rejected");
+            self.removed.push((member, weight));
+        }
+
+        #[ink(message)]
+        /// Record the admin change of a group
+        pub fn notify_admin_changed(&mut self, old_admin: AccountId, new_admin: AccountId) {
+            self.admin_changes.push((old_admin, new_admin));
+        }
+
+        #[ink(message)]
+        /// Return the removed members notified, oldest first
+        pub fn removed(&self) -> Vec<(AccountId, u64)> {
+            self.removed.clone()
+        }
+
+        #[ink(message)]
+        /// Return the admin changes notified, oldest first
+        pub fn admin_changes(&self) -> Vec<(AccountId, AccountId)> {
+            self.admin_changes.clone()
+        }
+
+        #[ink(message)]
+        /// Return the number of distinct members across `groups`
+        pub fn unique_member_count(&self, groups: Vec<AccountId>) -> Result<u32, InkGroupError> {
+            InkGroupClient::unique_member_count(&groups)
+        }
+    }
+
+    impl super::PSP22 for InkGroupMock {
+        #[ink(message)]
+        fn balance_of(&self, owner: AccountId) -> Balance {
+            self.balances.get(owner).unwrap_or_default()
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use crate::PSP22;
+
+        #[ink::test]
+        /// The mock records the notifications and serves the balances
+        fn mock_works() {
+            let owner = AccountId::from([0x01; 32]);
+            let mut mock = InkGroupMock::new();
+            assert_eq!(mock.balance_of(owner), 0);
+            mock.set_balance(owner, 42);
+            assert_eq!(mock.balance_of(owner), 42);
+            mock.notify_removed(owner, 3);
+            assert_eq!(mock.removed(), vec![(owner, 3)]);
+            mock.notify_admin_changed(owner, AccountId::from([0x02; 32]));
+            assert_eq!(mock.admin_changes().len(), 1);
+        }
+    }
+}
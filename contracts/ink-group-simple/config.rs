@@ -0,0 +1,131 @@
+/// Configuration of the group set at construction
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct GroupConfig {
+    /// Maximum total voting power of the group (uncapped if `None`)
+    pub max_total_weight: Option<u64>,
+    /// Maximum weight of a single member (uncapped if `None`)
+    pub max_member_weight: Option<u64>,
+    /// Reject any change by the caller of its own weight
+    pub forbid_self_weight_change: bool,
+    /// Reject the updates of a member to the weight it already has
+    pub reject_noop_updates: bool,
+    /// Notify the old and new admin contracts of an admin change
+    pub notify_admin_change: bool,
+    /// Allow the members to leave the group on their own
+    pub allow_self_leave: bool,
+    /// Keep the admin out of the members (separation of duties)
+    pub admin_must_not_be_member: bool,
+    /// Require the admin to be a contract (e.g. a multisig or a DAO)
+    pub require_contract_admin: bool,
+    /// Blocks between two weight changes of a member (no cooldown if 0)
+    pub weight_change_cooldown_blocks: u32,
+    /// Rescale the initial weights to sum to this total (kept as entered if `None`)
+    pub normalize_to: Option<u64>,
+    /// Maximum number of entries of a batch update (`MAX_BATCH_SIZE` if `None`)
+    pub max_batch_size: Option<u32>,
+    /// Total voting power at which the group is activated (no events if `None`)
+    pub activation_threshold: Option<u64>,
+    /// Blocks between two admin changes (no cooldown if 0)
+    pub admin_change_cooldown_blocks: u32,
+    /// Reject a construction whose total voting power is zero
+    pub require_positive_total: bool,
+    /// Maximum number of members of the group (uncapped if `None`)
+    pub max_members: Option<u32>,
+    /// Also record the last membership changes in storage
+    pub mirror_events: bool,
+    /// Maximum share of the total voting power of a single member, in percent (uncapped if `None`)
+    pub max_member_percent: Option<u32>,
+    /// Store the member weights as `u32` (see `Member<u32>`), rejecting larger weights
+    pub compact_weights: bool,
+}
+
+/// Changes to apply at once to the configuration with `update_config`, `None` keeps the field
+/// unchanged (`Some(None)` removes an optional limit). `normalize_to` only applies at
+/// construction and `compact_weights` fixes the storage format, so they can't be patched.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+pub struct GroupConfigPatch {
+    /// New maximum total voting power (`Some(None)` removes the cap)
+    pub max_total_weight: Option<Option<u64>>,
+    /// New maximum weight of a single member (`Some(None)` removes the cap)
+    pub max_member_weight: Option<Option<u64>>,
+    /// Reject any change by the caller of its own weight
+    pub forbid_self_weight_change: Option<bool>,
+    /// Reject the updates of a member to the weight it already has
+    pub reject_noop_updates: Option<bool>,
+    /// Notify the old and new admin contracts of an admin change
+    pub notify_admin_change: Option<bool>,
+    /// Allow the members to leave the group on their own
+    pub allow_self_leave: Option<bool>,
+    /// Keep the admin out of the members, checked against the current members
+    pub admin_must_not_be_member: Option<bool>,
+    /// Require the admin to be a contract, checked against the current admin
+    pub require_contract_admin: Option<bool>,
+    /// New blocks between two weight changes of a member (no cooldown if 0)
+    pub weight_change_cooldown_blocks: Option<u32>,
+    /// New maximum number of entries of a batch update, clamped like `set_max_batch_size`
+    /// (`Some(None)` restores `MAX_BATCH_SIZE`)
+    pub max_batch_size: Option<Option<u32>>,
+    /// New total voting power at which the group is activated, at most `max_total_weight`
+    /// (`Some(None)` disables the events)
+    pub activation_threshold: Option<Option<u64>>,
+    /// New blocks between two admin changes (no cooldown if 0)
+    pub admin_change_cooldown_blocks: Option<u32>,
+    /// Reject a construction whose total voting power is zero (no effect after the construction)
+    pub require_positive_total: Option<bool>,
+    /// New maximum number of members (`Some(None)` removes the cap)
+    pub max_members: Option<Option<u32>>,
+    /// Also record the next membership changes in storage
+    pub mirror_events: Option<bool>,
+    /// New maximum share of the total voting power of a single member, in percent
+    /// (`Some(None)` removes the cap)
+    pub max_member_percent: Option<Option<u32>>,
+}
+
+impl GroupConfig {
+    /// Return the configuration with the fields set in `patch` replaced
+    pub fn patched(self, patch: GroupConfigPatch) -> Self {
+        Self {
+            max_total_weight: patch.max_total_weight.unwrap_or(self.max_total_weight),
+            max_member_weight: patch.max_member_weight.unwrap_or(self.max_member_weight),
+            forbid_self_weight_change: patch
+                .forbid_self_weight_change
+                .unwrap_or(self.forbid_self_weight_change),
+            reject_noop_updates: patch
+                .reject_noop_updates
+                .unwrap_or(self.reject_noop_updates),
+            notify_admin_change: patch
+                .notify_admin_change
+                .unwrap_or(self.notify_admin_change),
+            allow_self_leave: patch.allow_self_leave.unwrap_or(self.allow_self_leave),
+            admin_must_not_be_member: patch
+                .admin_must_not_be_member
+                .unwrap_or(self.admin_must_not_be_member),
+            require_contract_admin: patch
+                .require_contract_admin
+                .unwrap_or(self.require_contract_admin),
+            weight_change_cooldown_blocks: patch
+                .weight_change_cooldown_blocks
+                .unwrap_or(self.weight_change_cooldown_blocks),
+            normalize_to: self.normalize_to,
+            max_batch_size: patch.max_batch_size.unwrap_or(self.max_batch_size),
+            activation_threshold: patch
+                .activation_threshold
+                .unwrap_or(self.activation_threshold),
+            admin_change_cooldown_blocks: patch
+                .admin_change_cooldown_blocks
+                .unwrap_or(self.admin_change_cooldown_blocks),
+            require_positive_total: patch
+                .require_positive_total
+                .unwrap_or(self.require_positive_total),
+            max_members: patch.max_members.unwrap_or(self.max_members),
+            mirror_events: patch.mirror_events.unwrap_or(self.mirror_events),
+            max_member_percent: patch.max_member_percent.unwrap_or(self.max_member_percent),
+            compact_weights: self.compact_weights,
+        }
+    }
+}
@@ -1,20 +1,45 @@
 #![cfg_attr(not(feature = "std"), no_std)]
+mod config;
 mod error;
 mod helpers;
+mod members;
+mod record;
 
 #[ink::contract]
 mod ink_group_simple {
-    use ink::prelude::vec::Vec;
-    use ink::storage::Lazy;
-    use ink_group::{InkGroup, InkGroupError, Member};
+    use ink::prelude::{vec, vec::Vec};
+    use ink::storage::{Lazy, Mapping};
+    use ink_group::{
+        features, selectors, GroupHealth, InkGroup, InkGroupError, Member, Role, SyncPage,
+        TotalWeight, UpdateReport,
+    };
 
-    use crate::{ensure, error::ContractError, helpers::validate_unique_members};
+    #[cfg(feature = "cross-contract")]
+    use crate::helpers::balance_to_weight;
+    use crate::{
+        config::{GroupConfig, GroupConfigPatch},
+        ensure,
+        error::ContractError,
+        helpers::{
+            blake2x256, clamp_batch_size, is_zero_address, member_leaf, merkle_root,
+            normalize_weights, push_csv_row, rank_order, validate_activation_threshold,
+            validate_concentration, validate_unique_addresses, validate_unique_members,
+            MAX_ADMIN_ACTIONS, MAX_BATCH_SIZE, MAX_BUCKET_BOUNDARIES, MAX_CAP_HISTORY,
+            MAX_CHANGE_RECORDS, MAX_GINI_MEMBERS, MAX_LOOKUP_ACCOUNTS, MAX_PIVOTAL_MEMBERS,
+            STORAGE_OVERHEAD_BYTES,
+        },
+        members::MemberList,
+        record::{ActionKind, ActionRecord, ChangeKind, ChangeRecord},
+    };
+    use ink::env::call::{build_call, ExecutionInput, Selector};
     /// Emitted when a member is added to the group
     #[ink(event)]
     pub struct MemberAddition {
         /// The member that was added.
         #[ink(topic)]
         member: AccountId,
+        /// The weight of the member that was added.
+        weight: u64,
     }
 
     /// Emitted when a member is removed to the group
@@ -23,6 +48,8 @@ mod ink_group_simple {
         /// The member that was removed.
         #[ink(topic)]
         member: AccountId,
+        /// The weight of the member that was removed.
+        weight: u64,
     }
 
     /// Emitted when a member is updated
@@ -44,13 +71,106 @@ mod ink_group_simple {
         new_admin: AccountId,
     }
 
+    /// Emitted when the cap on the total voting power is updated
+    #[ink(event)]
+    pub struct WeightCapUpdated {
+        /// The old cap (`None` if uncapped).
+        old_cap: Option<u64>,
+        /// The new cap (`None` if uncapped).
+        new_cap: Option<u64>,
+    }
+
+    /// Emitted when the configuration is updated at once
+    #[ink(event)]
+    pub struct ConfigUpdated {
+        /// The configuration before the update.
+        old: GroupConfig,
+        /// The configuration after the update.
+        new: GroupConfig,
+    }
+
+    /// Emitted when the weight of a member is adjusted
+    #[ink(event)]
+    pub struct MemberWeightChanged {
+        /// The member whose weight changed.
+        #[ink(topic)]
+        member: AccountId,
+        /// The weight before the change.
+        old_weight: u64,
+        /// The weight after the change.
+        new_weight: u64,
+    }
+
+    /// Emitted when the membership is frozen or unfrozen
+    #[ink(event)]
+    pub struct MembershipFreezeUpdated {
+        /// If the membership is frozen after the update.
+        frozen: bool,
+    }
+
+    /// Change of the cap on the total voting power: the block of the change and the new cap
+    pub type CapChange = (u32, Option<u64>);
+
+    /// Emitted when the total voting power reaches the activation threshold
+    #[ink(event)]
+    pub struct ThresholdReached {
+        /// The activation threshold.
+        threshold: u64,
+    }
+
+    /// Emitted when the total voting power drops below the activation threshold
+    #[ink(event)]
+    pub struct ThresholdLost {
+        /// The activation threshold.
+        threshold: u64,
+    }
+
+    /// Emitted when the code of the contract is upgraded
+    #[ink(event)]
+    pub struct Upgraded {
+        /// The old code hash.
+        old: Hash,
+        /// The new code hash.
+        new: Hash,
+    }
+
     #[ink(storage)]
     #[derive(Default)]
     pub struct InkGroupSimple {
         /// admin of the group (can perform any action)
         admin: Lazy<AccountId>,
-        total_voting_power: u64,
-        members: Vec<Member>,
+        total_voting_power: TotalWeight,
+        members: MemberList,
+        /// block number of the last successful mutation (members or admin)
+        last_mutated: Lazy<u32>,
+        /// contract notified of every member removal
+        remove_hook: Option<AccountId>,
+        /// membership epoch, incremented on every membership change
+        epoch: Lazy<u64>,
+        /// configuration set at construction
+        config: Lazy<GroupConfig>,
+        /// role label of the members (meaning defined by the deployer)
+        member_roles: Mapping<AccountId, u8>,
+        /// epoch of the last weight change of the members (0 if unchanged since construction)
+        member_changed_at: Mapping<AccountId, u64>,
+        /// block of the last weight change of the members (for the cooldown)
+        member_changed_block: Mapping<AccountId, u32>,
+        /// members protected from removal
+        locked_members: Mapping<AccountId, ()>,
+        /// members can't be added or removed, only reweighted
+        membership_frozen: bool,
+        /// last changes of the cap on the total voting power (block, new cap)
+        cap_history: Lazy<Vec<CapChange>>,
+        /// whether the total voting power is at or above the activation threshold
+        threshold_met: bool,
+        /// block of the last admin change (for the cooldown, unset until the first change)
+        admin_changed_block: Lazy<u32>,
+        /// last membership changes, if mirrored
+        change_records: Lazy<Vec<ChangeRecord>>,
+        /// last admin actions
+        admin_actions: Lazy<Vec<ActionRecord>>,
+        /// hash of the construction parameters
+        genesis_hash: Lazy<[u8; 32]>,
     }
 
     impl InkGroupSimple {
@@ -60,242 +180,5941 @@ mod ink_group_simple {
         pub fn try_new(
             admin: Option<AccountId>,
             initial_members: Vec<Member>,
+        ) -> Result<Self, ContractError> {
+            Self::try_new_with_config(admin, initial_members, GroupConfig::default())
+        }
+
+        #[ink(constructor)]
+        /// Construct the contract like `try_new` from parallel vectors of addresses and weights
+        pub fn try_new_split(
+            admin: Option<AccountId>,
+            addresses: Vec<AccountId>,
+            weights: Vec<u64>,
+        ) -> Result<Self, ContractError> {
+            ensure!(
+                addresses.len() == weights.len(),
+                InkGroupError::LengthMismatch {}
+            );
+            let initial_members = addresses
+                .into_iter()
+                .zip(weights)
+                .map(|(addr, weight)| Member { addr, weight })
+                .collect();
+            Self::try_new(admin, initial_members)
+        }
+
+        #[ink(constructor)]
+        /// Construct the contract like `try_new` with a custom configuration
+        pub fn try_new_with_config(
+            admin: Option<AccountId>,
+            initial_members: Vec<Member>,
+            config: GroupConfig,
         ) -> Result<Self, ContractError> {
             // Check if the admin address is set and the number of new members is not zero
             let admin = admin.unwrap_or(Self::env().caller());
+            // The zero address would make the group unadministrable
+            ensure!(!is_zero_address(&admin), InkGroupError::InvalidAdmin {});
             if initial_members.is_empty() {
                 return Err(InkGroupError::ZeroMembers {}.into());
             }
-            // Check if there are not equal members addresses entered
-            validate_unique_members(&initial_members)?;
-            let mut instance = Self::default();
+            if let Some(percent) = config.max_member_percent {
+                ensure!(percent <= 100, InkGroupError::InvalidPercent {});
+            }
+            validate_activation_threshold(config.activation_threshold, config.max_total_weight)?;
+            let mut sorted_members = initial_members.clone();
+            sorted_members.sort_unstable_by_key(|member| member.addr);
+            let genesis_hash = blake2x256(&scale::Encode::encode(&(admin, sorted_members, config)));
+            let initial_members = match config.normalize_to {
+                Some(target) => normalize_weights(&initial_members, target)?,
+                None => initial_members,
+            };
+            let config = GroupConfig {
+                max_batch_size: config.max_batch_size.map(clamp_batch_size),
+                ..config
+            };
+            let mut instance = Self {
+                members: MemberList::new(config.compact_weights),
+                ..Default::default()
+            };
+            instance.config.set(&config);
+            instance.genesis_hash.set(&genesis_hash);
+            // Validate the initial members like the ones entered by `update_members`
+            instance.validate_members(&initial_members)?;
+            instance.ensure_member_count_cap(initial_members.len())?;
+            if config.admin_must_not_be_member {
+                ensure!(
+                    initial_members.iter().all(|member| member.addr != admin),
+                    InkGroupError::AdminCannotBeMember {}
+                );
+            }
+            if config.require_contract_admin {
+                ensure!(
+                    Self::env().is_contract(&admin),
+                    InkGroupError::AdminMustBeContract {}
+                );
+            }
             // Set the admin
             instance.admin.set(&admin);
             // Calculate the total voting power and Save to storage each member
-            let total_power: u64 = initial_members
-                .into_iter()
-                .map(|member| {
-                    instance.members.push(member);
-                    // Emit the event that the member was added
-                    Self::env().emit_event(MemberAddition {
-                        member: member.addr,
-                    });
-                    member.weight
-                })
-                .sum();
+            let mut total_power = TotalWeight::default();
+            for member in initial_members {
+                total_power.add_weight(member.weight)?;
+                instance.members.push(member);
+                // Emit the event that the member was added
+                Self::env().emit_event(MemberAddition {
+                    member: member.addr,
+                    weight: member.weight,
+                });
+                instance.record_change(ChangeKind::Added, member.addr, member.weight.into());
+            }
+            instance.ensure_total_weight_cap(total_power.get())?;
+            instance.ensure_concentration_limit(&instance.members, total_power.get())?;
+            if config.require_positive_total {
+                ensure!(total_power.get() > 0, InkGroupError::ZeroTotalWeight {});
+            }
             // Save to storage the total voting power
             instance.total_voting_power = total_power;
+            instance.update_threshold_met();
+            // Seed the last mutation with the construction block
+            instance.touch();
+            instance.epoch.set(&0);
             Ok(instance)
         }
-    }
 
-    impl InkGroup for InkGroupSimple {
+        #[cfg(feature = "cross-contract")]
         #[ink(message)]
-        /// Return current admin.
-        fn get_admin(&self) -> Result<AccountId, InkGroupError> {
-            // Should always be some admin in case of error the logic of the contract is wrong
-            let admin = self.admin.get().ok_or(InkGroupError::LogicErr {})?;
-            Ok(admin)
+        /// Set the weight of every member to its balance of the PSP22 `token` (only admin can).
+        /// Balances above `u64::MAX` are capped to `u64::MAX`.
+        pub fn sync_weights_from_token(&mut self, token: AccountId) -> Result<(), InkGroupError> {
+            self.sync_weights(token, u64::MAX)
         }
 
+        #[cfg(feature = "cross-contract")]
         #[ink(message)]
-        /// Return all members info.
-        fn get_members(&self) -> Result<Vec<Member>, InkGroupError> {
-            // Should always be some member in case of error the logic of the contract is
-            // wrong
-            if self.members.is_empty() {
-                return Err(InkGroupError::LogicErr {});
-            }
-            Ok(self.members.clone())
+        /// Set the weight of every member to its balance of the PSP22 `token` capped to
+        /// `per_member_cap` (only admin can).
+        pub fn sync_weights_capped(
+            &mut self,
+            token: AccountId,
+            per_member_cap: u64,
+        ) -> Result<(), InkGroupError> {
+            self.sync_weights(token, per_member_cap)
         }
 
-        #[ink(message)]
-        /// Return member info searched by address.
-        fn get_member(&self, member: AccountId) -> Result<Member, InkGroupError> {
-            // Return error in case of the member is not found in the group
-            let founded_member = self
-                .members
-                .iter()
-                .cloned()
-                .find(|&memb| memb.addr == member)
-                .ok_or(InkGroupError::NoMember {})?;
-            Ok(founded_member)
+        /// Set the weight of every member to its balance of the PSP22 `token`, capped to `cap`
+        /// (only admin can). The token is called without allowing reentry into the group.
+        /// Nothing is recorded when no weight changed.
+        #[cfg(feature = "cross-contract")]
+        fn sync_weights(&mut self, token: AccountId, cap: u64) -> Result<(), InkGroupError> {
+            let caller = self.env().caller();
+            let admin = self.get_admin()?;
+            ensure!(caller == admin, InkGroupError::Unauthorized {});
+            let mut total_power = TotalWeight::default();
+            let mut changed = false;
+            for index in 0..self.members.len() {
+                let member = self.members[index];
+                let balance = build_call::<Environment>()
+                    .call(token)
+                    .gas_limit(0)
+                    .exec_input(
+                        ExecutionInput::new(Selector::new(ink::selector_bytes!(
+                            "PSP22::balance_of"
+                        )))
+                        .push_arg(member.addr),
+                    )
+                    .returns::<Balance>()
+                    .try_invoke()??;
+                let weight = balance_to_weight(balance).min(cap);
+                self.ensure_member_weight_cap(weight)?;
+                total_power.add_weight(weight)?;
+                if weight != member.weight {
+                    self.ensure_cooldown_elapsed(member.addr)?;
+                    changed = true;
+                    self.members[index].weight = weight;
+                    self.mark_changed(member.addr);
+                    // Emit event that the member was updated
+                    self.env().emit_event(MemberUpdate {
+                        member: member.addr,
+                    });
+                    self.record_change(
+                        ChangeKind::Updated,
+                        member.addr,
+                        i128::from(weight) - i128::from(member.weight),
+                    );
+                }
+            }
+            if !changed {
+                return Ok(());
+            }
+            self.ensure_total_weight_cap(total_power.get())?;
+            self.ensure_concentration_limit(&self.members, total_power.get())?;
+            self.total_voting_power = total_power;
+            self.update_threshold_met();
+            self.bump_epoch();
+            self.record_action(ActionKind::MembersUpdated);
+            self.touch();
+            Ok(())
         }
 
         #[ink(message)]
-        /// Return the total voting power.
-        fn get_total_weight(&self) -> u64 {
-            self.total_voting_power
+        /// Return the contract notified of every member removal.
+        pub fn get_remove_hook(&self) -> Option<AccountId> {
+            self.remove_hook
         }
 
         #[ink(message)]
-        /// Change the admin (only current admin can).
-        fn update_admin(&mut self, new_admin: AccountId) -> Result<(), InkGroupError> {
+        /// Set (or unset with `None`) the contract notified of every member removal (only admin
+        /// can). The hook must expose a `notify_removed(member: AccountId, weight: u64)` message.
+        pub fn set_remove_hook(&mut self, hook: Option<AccountId>) -> Result<(), InkGroupError> {
             let caller = self.env().caller();
             let admin = self.get_admin()?;
             ensure!(caller == admin, InkGroupError::Unauthorized {});
-            self.admin.set(&new_admin);
-            // Emit event that the admin was updated
-            self.env().emit_event(AdminUpdate {
-                old_admin: admin,
-                new_admin,
-            });
+            self.remove_hook = hook;
+            self.record_action(ActionKind::ConfigUpdated);
+            self.touch();
             Ok(())
         }
 
-        #[ink(message)]
-        /// If an already existing address is entered, the voting power is updated. Remove is applied after add, so if an address is in both, it is removed.
-        fn update_members(
-            &mut self,
-            new_members: Vec<Member>,
-            remove_members: Vec<AccountId>,
-        ) -> Result<(), InkGroupError> {
-            let caller = self.env().caller();
-            let admin = self.get_admin()?;
-            ensure!(caller == admin, InkGroupError::Unauthorized {});
-            validate_unique_members(&new_members)?;
-            // for every new member check if already exist in the group, in that case update the voting power
-            // otherwise add the member to the group
-            for member in new_members {
-                if let Some(index) = self
-                    .members
-                    .iter()
-                    .position(|&old_member| old_member.addr == member.addr)
-                {
-                    // first subtract the old vote weight from the total
-                    self.total_voting_power -= self.members[index].weight;
-                    // then add the new vote weight to the total
-                    self.total_voting_power += member.weight;
-                    // last change the old vote weight of the member to the new
-                    self.members[index].weight = member.weight;
-                    // Emit event that the member was updated
-                    self.env().emit_event(MemberUpdate {
-                        member: self.members[index].addr,
-                    })
-                } else {
-                    // add the new member and then add the vote weight to the total
-                    self.members.push(member);
-                    // Emit the event that the member was added
-                    self.env().emit_event(MemberAddition {
-                        member: member.addr,
-                    });
-                    self.total_voting_power += member.weight;
-                }
+        /// Notify the remove hook (if any) that a member was removed. The call is made after the
+        /// removal and without allowing reentry, so the hook can't call back into the group.
+        fn notify_removed(&self, member: Member) -> Result<(), InkGroupError> {
+            if let Some(hook) = self.remove_hook {
+                build_call::<Environment>()
+                    .call(hook)
+                    .gas_limit(0)
+                    .exec_input(
+                        ExecutionInput::new(Selector::new(ink::selector_bytes!("notify_removed")))
+                            .push_arg(member.addr)
+                            .push_arg(member.weight),
+                    )
+                    .returns::<()>()
+                    .try_invoke()??;
             }
-            // for each member to be removed check that it actually already exists within the group
-            // and in this case first subtract the weight of the vote from the total and then
-            // delete the member otherwise do nothing
-            for member in remove_members {
-                if let Some(index) = self
-                    .members
-                    .iter()
-                    .position(|&old_member| old_member.addr == member)
-                {
-                    self.total_voting_power -= self.members[index].weight;
-                    let removed_member_addr = self.members[index].addr;
-                    self.members.remove(index);
-                    // Emit the event that the member was removed
-                    self.env().emit_event(MemberRemoval {
-                        member: removed_member_addr,
-                    });
+            Ok(())
+        }
+
+        /// Notify the old and new admin of an admin change, if enabled by the configuration and
+        /// they are contracts. The calls don't allow reentry, and their failure is ignored so that
+        /// an admin contract can't block the transfer.
+        fn notify_admin_changed(&self, old_admin: AccountId, new_admin: AccountId) {
+            if !self.config().notify_admin_change {
+                return;
+            }
+            let targets = if old_admin == new_admin {
+                vec![new_admin]
+            } else {
+                vec![old_admin, new_admin]
+            };
+            for target in targets {
+                if self.env().is_contract(&target) {
+                    let _ = build_call::<Environment>()
+                        .call(target)
+                        .gas_limit(0)
+                        .exec_input(
+                            ExecutionInput::new(Selector::new(ink::selector_bytes!(
+                                "notify_admin_changed"
+                            )))
+                            .push_arg(old_admin)
+                            .push_arg(new_admin),
+                        )
+                        .returns::<()>()
+                        .try_invoke();
                 }
             }
+        }
 
+        #[ink(message)]
+        /// Check the storage invariants: the admin is set, the member addresses are unique and
+        /// the total voting power is the sum of the member weights.
+        pub fn verify_invariants(&self) -> Result<(), InkGroupError> {
+            self.get_admin()?;
+            validate_unique_members(&self.members).map_err(|_| InkGroupError::LogicErr {})?;
+            let total_power = self
+                .members
+                .iter()
+                .try_fold(0u64, |total, member| total.checked_add(member.weight))
+                .ok_or(InkGroupError::LogicErr {})?;
+            ensure!(
+                total_power == self.total_voting_power.get(),
+                InkGroupError::LogicErr {}
+            );
             Ok(())
         }
-    }
 
-    #[cfg(test)]
-    mod tests {
-        use super::*;
-        use ink::env::test::{self, EmittedEvent};
+        /// Return the configuration set at construction
+        fn config(&self) -> GroupConfig {
+            self.config.get().unwrap_or_default()
+        }
 
-        type Event = <InkGroupSimple as ::ink::reflect::ContractEventBase>::Type;
+        /// Validate the members entered to be added or updated: the addresses must be unique and
+        /// the weights within the configured maximum member weight
+        fn validate_members(&self, members: &[Member]) -> Result<(), InkGroupError> {
+            validate_unique_members(members)?;
+            for member in members {
+                self.ensure_member_weight_cap(member.weight)?;
+            }
+            Ok(())
+        }
 
-        // Integration test setup
+        /// Check that members can be added or removed
+        fn ensure_membership_unfrozen(&self) -> Result<(), InkGroupError> {
+            ensure!(
+                !self.membership_frozen,
+                InkGroupError::InvalidState {
+                    reason: InkGroupError::STATE_MEMBERSHIP_FROZEN
+                }
+            );
+            Ok(())
+        }
 
-        fn default_accounts() -> test::DefaultAccounts<Environment> {
-            ink::env::test::default_accounts::<Environment>()
+        /// Check that `count` members don't exceed the configured maximum number of members
+        fn ensure_member_count_cap(&self, count: usize) -> Result<(), InkGroupError> {
+            if let Some(max) = self.config().max_members {
+                ensure!(count <= max as usize, InkGroupError::TooManyMembers { max });
+            }
+            Ok(())
         }
 
-        fn set_caller(sender: AccountId) {
-            ink::env::test::set_caller::<Environment>(sender);
+        /// Check that `weight` fits in the storage and doesn't exceed the configured maximum
+        /// member weight
+        fn ensure_member_weight_cap(&self, weight: u64) -> Result<(), InkGroupError> {
+            self.ensure_weight_storable(weight)?;
+            if let Some(cap) = self.config().max_member_weight {
+                ensure!(weight <= cap, InkGroupError::WeightCapExceeded { cap });
+            }
+            Ok(())
         }
 
-        fn build_contract() -> InkGroupSimple {
-            let accounts = default_accounts();
+        /// Check that `weight` fits in a `u32` if the weights are stored compact
+        fn ensure_weight_storable(&self, weight: u64) -> Result<(), InkGroupError> {
+            if self.members.is_compact() {
+                ensure!(
+                    u32::try_from(weight).is_ok(),
+                    InkGroupError::WeightTooLarge {}
+                );
+            }
+            Ok(())
+        }
 
-            let alice_member = Member {
-                addr: accounts.alice,
-                weight: 1,
-            };
-            let bob_member = Member {
-                addr: accounts.bob,
-                weight: 1,
-            };
+        /// Check that `total_power` doesn't exceed the configured maximum total voting power
+        fn ensure_total_weight_cap(&self, total_power: u64) -> Result<(), InkGroupError> {
+            if let Some(cap) = self.config().max_total_weight {
+                ensure!(
+                    total_power <= cap,
+                    InkGroupError::TotalWeightCapExceeded { cap }
+                );
+            }
+            Ok(())
+        }
 
-            let members = vec![alice_member, bob_member];
+        /// Check that no member of `members` exceeds the configured share of `total_power`
+        fn ensure_concentration_limit(
+            &self,
+            members: &[Member],
+            total_power: u64,
+        ) -> Result<(), InkGroupError> {
+            if let Some(percent) = self.config().max_member_percent {
+                validate_concentration(members, total_power, percent)?;
+            }
+            Ok(())
+        }
 
-            set_caller(alice_member.addr);
+        /// Record that the weight of `member` changes in the epoch about to be bumped to
+        fn mark_changed(&mut self, member: AccountId) {
+            let epoch = self.current_epoch() + 1;
+            self.member_changed_at.insert(member, &epoch);
+            let block = self.env().block_number();
+            self.member_changed_block.insert(member, &block);
+        }
 
-            InkGroupSimple::try_new(None, members).unwrap()
+        /// Check that the weight change cooldown of `member` has elapsed
+        fn ensure_cooldown_elapsed(&self, member: AccountId) -> Result<(), InkGroupError> {
+            let cooldown = self.config().weight_change_cooldown_blocks;
+            if cooldown == 0 {
+                return Ok(());
+            }
+            if let Some(last_change) = self.member_changed_block.get(member) {
+                let available_at = last_change.saturating_add(cooldown);
+                ensure!(
+                    self.env().block_number() >= available_at,
+                    InkGroupError::CooldownActive { available_at }
+                );
+            }
+            Ok(())
         }
 
-        fn decode_events(emittend_events: Vec<EmittedEvent>) -> Vec<Event> {
-            emittend_events
-                .into_iter()
-                .map(|event| {
-                    <Event as scale::Decode>::decode(&mut &event.data[..]).expect("invalid data")
-                })
-                .collect()
+        /// Check that `new_admin` can become the admin
+        fn ensure_valid_new_admin(&self, new_admin: AccountId) -> Result<(), InkGroupError> {
+            ensure!(!is_zero_address(&new_admin), InkGroupError::InvalidAdmin {});
+            if self.config().admin_must_not_be_member {
+                ensure!(
+                    self.members.iter().all(|member| member.addr != new_admin),
+                    InkGroupError::AdminCannotBeMember {}
+                );
+            }
+            if self.config().require_contract_admin {
+                ensure!(
+                    self.env().is_contract(&new_admin),
+                    InkGroupError::AdminMustBeContract {}
+                );
+            }
+            self.ensure_admin_cooldown_elapsed()?;
+            Ok(())
         }
 
-        #[ink::test]
-        /// The default constructor does its job.
-        fn construction_works() {
-            let accounts = default_accounts();
-            let alice_member = Member {
-                addr: accounts.alice,
-                weight: 1,
-            };
-            let bob_member = Member {
-                addr: accounts.bob,
-                weight: 1,
-            };
-            let charlie_member = Member {
-                addr: accounts.charlie,
-                weight: 1,
-            };
-            let members = vec![alice_member, bob_member];
-            let contract = build_contract();
+        /// Make `new_admin` the admin in place of `admin`, once every check passed
+        fn transfer_admin(&mut self, admin: AccountId, new_admin: AccountId) {
+            self.admin.set(&new_admin);
+            self.admin_changed_block.set(&self.env().block_number());
+            self.record_action(ActionKind::AdminUpdated);
+            self.touch();
+            // Emit event that the admin was updated
+            self.env().emit_event(AdminUpdate {
+                old_admin: admin,
+                new_admin,
+            });
+            self.notify_admin_changed(admin, new_admin);
+        }
 
-            let emittend_events: Vec<EmittedEvent> = ink::env::test::recorded_events().collect();
-            let decoded_events = decode_events(emittend_events);
-            if let Event::MemberAddition(MemberAddition { member }) = decoded_events[0] {
-                assert_eq!(member, accounts.alice);
-            } else {
-                panic!("encountered unexpected event kind: expected a MemberAddition event")
+        /// Record a membership change in the ring buffer, if the events are mirrored
+        fn record_change(&mut self, kind: ChangeKind, member: AccountId, weight_delta: i128) {
+            if !self.config().mirror_events {
+                return;
             }
+            let mut records = self.change_records.get().unwrap_or_default();
+            if records.len() == MAX_CHANGE_RECORDS {
+                records.remove(0);
+            }
+            records.push(ChangeRecord {
+                kind,
+                member,
+                weight_delta,
+                block: self.env().block_number(),
+            });
+            self.change_records.set(&records);
+        }
 
-            assert_eq!(contract.members.len(), 2);
-            assert_eq!(contract.admin.get().unwrap(), accounts.alice);
-            assert!(contract.members.iter().eq(members.iter()));
-            assert!(contract.members.contains(&alice_member));
-            assert!(!contract.members.contains(&charlie_member));
+        /// Record an admin action in the ring buffer, the oldest being evicted first
+        fn record_action(&mut self, kind: ActionKind) {
+            let mut actions = self.admin_actions.get().unwrap_or_default();
+            if actions.len() == MAX_ADMIN_ACTIONS {
+                actions.remove(0);
+            }
+            actions.push(ActionRecord {
+                actor: self.env().caller(),
+                kind,
+                block: self.env().block_number(),
+            });
+            self.admin_actions.set(&actions);
         }
 
-        #[ink::test]
-        /// Get the current admin of the group
-        fn get_admin_works() {
-            let accounts = default_accounts();
-            let contract = build_contract();
-            let response = InkGroupSimple::get_admin(&contract).unwrap();
-            assert_eq!(response, accounts.alice);
+        /// Check that the admin change cooldown has elapsed
+        fn ensure_admin_cooldown_elapsed(&self) -> Result<(), InkGroupError> {
+            let cooldown = self.config().admin_change_cooldown_blocks;
+            if cooldown == 0 {
+                return Ok(());
+            }
+            if let Some(last_change) = self.admin_changed_block.get() {
+                let available_at = last_change.saturating_add(cooldown);
+                ensure!(
+                    self.env().block_number() >= available_at,
+                    InkGroupError::AdminCooldownActive { available_at }
+                );
+            }
+            Ok(())
+        }
+
+        /// Emit `ThresholdReached` or `ThresholdLost` if the total voting power crossed the
+        /// activation threshold since the last check
+        fn update_threshold_met(&mut self) {
+            if let Some(threshold) = self.config().activation_threshold {
+                let met = self.total_voting_power.get() >= threshold;
+                if met != self.threshold_met {
+                    self.threshold_met = met;
+                    if met {
+                        self.env().emit_event(ThresholdReached { threshold });
+                    } else {
+                        self.env().emit_event(ThresholdLost { threshold });
+                    }
+                }
+            }
+        }
+
+        /// Increment the membership epoch
+        fn bump_epoch(&mut self) {
+            let epoch = self.current_epoch() + 1;
+            self.epoch.set(&epoch);
+        }
+
+        #[ink(message)]
+        /// Set (or remove with `None`) the cap on the total voting power (only admin can). A cap
+        /// below the current total voting power or the activation threshold is rejected.
+        pub fn set_total_weight_cap(&mut self, cap: Option<u64>) -> Result<(), InkGroupError> {
+            let caller = self.env().caller();
+            let admin = self.get_admin()?;
+            ensure!(caller == admin, InkGroupError::Unauthorized {});
+            if let Some(cap) = cap {
+                ensure!(
+                    self.total_voting_power.get() <= cap,
+                    InkGroupError::TotalWeightCapExceeded { cap }
+                );
+            }
+            let mut config = self.config();
+            validate_activation_threshold(config.activation_threshold, cap)?;
+            let old_cap = config.max_total_weight;
+            config.max_total_weight = cap;
+            self.config.set(&config);
+            self.record_cap_change(old_cap, cap);
+            self.record_action(ActionKind::ConfigUpdated);
+            self.touch();
+            Ok(())
+        }
+
+        /// Append the new cap on the total voting power to the history and emit
+        /// `WeightCapUpdated`
+        fn record_cap_change(&mut self, old_cap: Option<u64>, new_cap: Option<u64>) {
+            let mut history = self.cap_history();
+            if history.len() == MAX_CAP_HISTORY {
+                history.remove(0);
+            }
+            history.push((self.env().block_number(), new_cap));
+            self.cap_history.set(&history);
+            self.env().emit_event(WeightCapUpdated { old_cap, new_cap });
+        }
+
+        #[ink(message)]
+        /// Set the maximum number of entries of a batch update (only admin can), clamped between
+        /// 1 and `MAX_BATCH_SIZE`.
+        pub fn set_max_batch_size(&mut self, size: u32) -> Result<(), InkGroupError> {
+            let caller = self.env().caller();
+            let admin = self.get_admin()?;
+            ensure!(caller == admin, InkGroupError::Unauthorized {});
+            let mut config = self.config();
+            config.max_batch_size = Some(clamp_batch_size(size));
+            self.config.set(&config);
+            self.record_action(ActionKind::ConfigUpdated);
+            self.touch();
+            Ok(())
+        }
+
+        #[ink(message)]
+        /// Apply the fields set in `patch` to the configuration at once (only admin can). The
+        /// whole patched configuration is validated first, so an inconsistent patch changes
+        /// nothing.
+        pub fn update_config(&mut self, patch: GroupConfigPatch) -> Result<(), InkGroupError> {
+            let caller = self.env().caller();
+            let admin = self.get_admin()?;
+            ensure!(caller == admin, InkGroupError::Unauthorized {});
+            let old = self.config();
+            let mut new = old.patched(patch);
+            new.max_batch_size = new.max_batch_size.map(clamp_batch_size);
+            self.validate_config(&new, admin)?;
+
+            self.config.set(&new);
+            if new.max_total_weight != old.max_total_weight {
+                self.record_cap_change(old.max_total_weight, new.max_total_weight);
+            }
+            if new.activation_threshold != old.activation_threshold {
+                // Report the transitions against the new threshold from scratch, closing the
+                // reached state of the old one first so that the listeners stay in sync
+                if self.threshold_met {
+                    if let Some(threshold) = old.activation_threshold {
+                        self.env().emit_event(ThresholdLost { threshold });
+                    }
+                    self.threshold_met = false;
+                }
+                self.update_threshold_met();
+            }
+            self.env().emit_event(ConfigUpdated { old, new });
+            self.record_action(ActionKind::ConfigUpdated);
+            self.touch();
+            Ok(())
+        }
+
+        /// Check that `config` is consistent and holds for the current admin and members
+        fn validate_config(
+            &self,
+            config: &GroupConfig,
+            admin: AccountId,
+        ) -> Result<(), InkGroupError> {
+            if let Some(percent) = config.max_member_percent {
+                ensure!(percent <= 100, InkGroupError::InvalidPercent {});
+            }
+            validate_activation_threshold(config.activation_threshold, config.max_total_weight)?;
+            let total_power = self.total_voting_power.get();
+            if let Some(cap) = config.max_total_weight {
+                ensure!(
+                    total_power <= cap,
+                    InkGroupError::TotalWeightCapExceeded { cap }
+                );
+            }
+            if let Some(cap) = config.max_member_weight {
+                ensure!(
+                    self.members.iter().all(|member| member.weight <= cap),
+                    InkGroupError::WeightCapExceeded { cap }
+                );
+            }
+            if let Some(max) = config.max_members {
+                ensure!(
+                    self.members.len() <= max as usize,
+                    InkGroupError::TooManyMembers { max }
+                );
+            }
+            if let Some(percent) = config.max_member_percent {
+                validate_concentration(&self.members, total_power, percent)?;
+            }
+            if config.admin_must_not_be_member {
+                ensure!(
+                    self.members.iter().all(|member| member.addr != admin),
+                    InkGroupError::AdminCannotBeMember {}
+                );
+            }
+            if config.require_contract_admin {
+                ensure!(
+                    self.env().is_contract(&admin),
+                    InkGroupError::AdminMustBeContract {}
+                );
+            }
+            Ok(())
+        }
+
+        /// Check that a batch of `len` entries doesn't exceed the maximum batch size
+        fn ensure_batch_size(&self, len: usize) -> Result<(), InkGroupError> {
+            let max = self
+                .config()
+                .max_batch_size
+                .unwrap_or(MAX_BATCH_SIZE as u32);
+            ensure!(len <= max as usize, InkGroupError::InputTooLong { max });
+            Ok(())
+        }
+
+        #[ink(message)]
+        /// Return the role label of a member (`None` if not set).
+        pub fn get_member_role(&self, member: AccountId) -> Option<u8> {
+            self.member_roles.get(member)
+        }
+
+        #[ink(message)]
+        /// Return the role label of each account, by position (at most `MAX_LOOKUP_ACCOUNTS`
+        /// accounts).
+        pub fn get_roles(
+            &self,
+            accounts: Vec<AccountId>,
+        ) -> Result<Vec<Option<u8>>, InkGroupError> {
+            ensure!(
+                accounts.len() <= MAX_LOOKUP_ACCOUNTS,
+                InkGroupError::InputTooLong {
+                    max: MAX_LOOKUP_ACCOUNTS as u32
+                }
+            );
+            // The roles are cleared on removal, so a non-member has none
+            Ok(accounts
+                .into_iter()
+                .map(|account| self.member_roles.get(account))
+                .collect())
+        }
+
+        #[ink(message)]
+        /// Return the total weight of the members with the given role label.
+        pub fn role_weight(&self, role: u8) -> u64 {
+            // The total voting power fits in a u64, so does the weight of any subset of members
+            self.members
+                .iter()
+                .filter(|member| self.member_roles.get(member.addr) == Some(role))
+                .map(|member| member.weight)
+                .sum()
+        }
+
+        #[ink(message)]
+        /// Return the share of a member of the weight of its role in basis points (error
+        /// `NoMember` if the address is not a member with the given role label).
+        pub fn role_share_bps(&self, member: AccountId, role: u8) -> Result<u32, InkGroupError> {
+            ensure!(
+                self.member_roles.get(member) == Some(role),
+                InkGroupError::NoMember {}
+            );
+            let weight = self.get_member(member)?.weight;
+            let total = u128::from(self.role_weight(role));
+            if total == 0 {
+                return Ok(0);
+            }
+            // The weight of a member is at most the weight of its role, so the share fits in a u32
+            let bps = u128::from(weight)
+                .checked_mul(10_000)
+                .ok_or(InkGroupError::WeightOverflow {})?
+                / total;
+            u32::try_from(bps).map_err(|_| InkGroupError::LogicErr {})
+        }
+
+        #[ink(message)]
+        /// Set the role label of a member (only admin can). The label doesn't affect the voting
+        /// power and is cleared when the member is removed.
+        pub fn set_member_role(
+            &mut self,
+            member: AccountId,
+            role: u8,
+        ) -> Result<(), InkGroupError> {
+            let caller = self.env().caller();
+            let admin = self.get_admin()?;
+            ensure!(caller == admin, InkGroupError::Unauthorized {});
+            self.get_member(member)?;
+            self.member_roles.insert(member, &role);
+            self.record_action(ActionKind::RolesUpdated);
+            self.touch();
+            Ok(())
+        }
+
+        #[ink(message)]
+        /// Add a new member with its role label in one call (only admin can).
+        pub fn add_member_with_role(
+            &mut self,
+            member: Member,
+            role: u8,
+        ) -> Result<(), InkGroupError> {
+            let caller = self.env().caller();
+            let admin = self.get_admin()?;
+            ensure!(caller == admin, InkGroupError::Unauthorized {});
+            // Unlike `update_members` this never updates a member
+            ensure!(
+                self.get_member(member.addr).is_err(),
+                InkGroupError::DuplicateMember {
+                    member: member.addr
+                }
+            );
+            self.update_members(vec![member], vec![])?;
+            self.member_roles.insert(member.addr, &role);
+            Ok(())
+        }
+
+        #[ink(message)]
+        /// Set the role label of many members at once, all or nothing (only admin can).
+        pub fn set_roles(
+            &mut self,
+            assignments: Vec<(AccountId, u8)>,
+        ) -> Result<(), InkGroupError> {
+            let caller = self.env().caller();
+            let admin = self.get_admin()?;
+            ensure!(caller == admin, InkGroupError::Unauthorized {});
+            self.ensure_batch_size(assignments.len())?;
+            let addresses: Vec<AccountId> = assignments.iter().map(|(addr, _)| *addr).collect();
+            validate_unique_addresses(&addresses)?;
+            // Check every address before setting any role
+            for addr in addresses.iter() {
+                self.get_member(*addr)?;
+            }
+            for (member, role) in assignments {
+                if self.member_roles.get(member) != Some(role) {
+                    self.member_roles.insert(member, &role);
+                    self.env().emit_event(MemberUpdate { member });
+                }
+            }
+            self.record_action(ActionKind::RolesUpdated);
+            self.touch();
+            Ok(())
+        }
+
+        #[ink(message)]
+        /// Merge the entries of the addresses stored more than once into the first one, summing
+        /// their weights, recompute the total voting power and return the number of entries
+        /// removed (only admin can). Repairs a storage corrupted by the old duplicate check.
+        pub fn dedup_members(&mut self) -> Result<u32, InkGroupError> {
+            let caller = self.env().caller();
+            let admin = self.get_admin()?;
+            ensure!(caller == admin, InkGroupError::Unauthorized {});
+            let mut members: Vec<Member> = Vec::with_capacity(self.members.len());
+            // The address and the weight before the merge of every entry kept
+            let mut merged: Vec<(AccountId, u64)> = Vec::new();
+            let mut removed: u32 = 0;
+            for member in self.members.iter() {
+                if let Some(kept) = members.iter_mut().find(|kept| kept.addr == member.addr) {
+                    if !merged.iter().any(|(addr, _)| *addr == kept.addr) {
+                        merged.push((kept.addr, kept.weight));
+                    }
+                    kept.weight = kept
+                        .weight
+                        .checked_add(member.weight)
+                        .ok_or(InkGroupError::WeightOverflow {})?;
+                    self.ensure_member_weight_cap(kept.weight)?;
+                    removed += 1;
+                } else {
+                    members.push(*member);
+                }
+            }
+            let mut total_power = TotalWeight::default();
+            for member in members.iter() {
+                total_power.add_weight(member.weight)?;
+            }
+            self.ensure_concentration_limit(&members, total_power.get())?;
+            // No address leaves the group: the entries kept are reported as weight changes, so
+            // that the events still sum to the total voting power
+            let changes: Vec<(AccountId, u64, u64)> = merged
+                .into_iter()
+                .filter_map(|(addr, old_weight)| {
+                    members
+                        .iter()
+                        .find(|member| member.addr == addr)
+                        .map(|member| (addr, old_weight, member.weight))
+                })
+                .collect();
+            *self.members = members;
+            self.total_voting_power = total_power;
+            for (member, old_weight, new_weight) in changes {
+                self.mark_changed(member);
+                self.env().emit_event(MemberWeightChanged {
+                    member,
+                    old_weight,
+                    new_weight,
+                });
+                self.record_change(
+                    ChangeKind::Updated,
+                    member,
+                    i128::from(new_weight) - i128::from(old_weight),
+                );
+            }
+            if removed > 0 {
+                self.update_threshold_met();
+                self.bump_epoch();
+                self.record_action(ActionKind::MembersUpdated);
+                self.touch();
+            }
+            Ok(removed)
+        }
+
+        #[ink(message)]
+        /// Return if a member is locked against removal.
+        pub fn is_member_locked(&self, member: AccountId) -> bool {
+            self.locked_members.contains(member)
+        }
+
+        #[ink(message)]
+        /// Lock a member against removal (only admin can). Its weight can still be updated.
+        pub fn lock_member(&mut self, member: AccountId) -> Result<(), InkGroupError> {
+            let caller = self.env().caller();
+            let admin = self.get_admin()?;
+            ensure!(caller == admin, InkGroupError::Unauthorized {});
+            self.get_member(member)?;
+            self.locked_members.insert(member, &());
+            self.record_action(ActionKind::LocksUpdated);
+            self.touch();
+            Ok(())
+        }
+
+        #[ink(message)]
+        /// Unlock a member, so that it can be removed again (only admin can).
+        pub fn unlock_member(&mut self, member: AccountId) -> Result<(), InkGroupError> {
+            let caller = self.env().caller();
+            let admin = self.get_admin()?;
+            ensure!(caller == admin, InkGroupError::Unauthorized {});
+            self.locked_members.remove(member);
+            self.record_action(ActionKind::LocksUpdated);
+            self.touch();
+            Ok(())
+        }
+
+        #[ink(message)]
+        /// Return if the membership is frozen.
+        pub fn is_membership_frozen(&self) -> bool {
+            self.membership_frozen
+        }
+
+        #[ink(message)]
+        /// Forbid adding and removing members, the weights can still be updated (only admin
+        /// can).
+        pub fn freeze_membership(&mut self) -> Result<(), InkGroupError> {
+            self.set_membership_frozen(true)
+        }
+
+        #[ink(message)]
+        /// Allow adding and removing members again (only admin can).
+        pub fn unfreeze_membership(&mut self) -> Result<(), InkGroupError> {
+            self.set_membership_frozen(false)
+        }
+
+        fn set_membership_frozen(&mut self, frozen: bool) -> Result<(), InkGroupError> {
+            let caller = self.env().caller();
+            let admin = self.get_admin()?;
+            ensure!(caller == admin, InkGroupError::Unauthorized {});
+            self.membership_frozen = frozen;
+            self.record_action(ActionKind::FreezeUpdated);
+            self.touch();
+            self.env().emit_event(MembershipFreezeUpdated { frozen });
+            Ok(())
+        }
+
+        #[ink(message)]
+        /// Return the configuration of the group.
+        pub fn get_config(&self) -> GroupConfig {
+            self.config()
+        }
+
+        #[ink(message)]
+        /// Return the changes of the cap on the total voting power, oldest first, as the block of
+        /// the change and the new cap (at most `MAX_CAP_HISTORY`).
+        pub fn cap_history(&self) -> Vec<CapChange> {
+            self.cap_history.get().unwrap_or_default()
+        }
+
+        #[ink(message)]
+        /// Return the last `limit` membership changes, oldest first (empty if the events are not
+        /// mirrored).
+        pub fn recent_changes(&self, limit: u32) -> Vec<ChangeRecord> {
+            let records = self.change_records.get().unwrap_or_default();
+            let start = records.len().saturating_sub(limit as usize);
+            records[start..].to_vec()
+        }
+
+        #[ink(message)]
+        /// Return the last `limit` admin actions, oldest first.
+        pub fn admin_actions(&self, limit: u32) -> Vec<ActionRecord> {
+            let actions = self.admin_actions.get().unwrap_or_default();
+            let start = actions.len().saturating_sub(limit as usize);
+            actions[start..].to_vec()
+        }
+
+        #[ink(message)]
+        /// Replace the code of the contract, keeping its storage (only admin can).
+        pub fn upgrade(&mut self, new_code_hash: Hash) -> Result<(), InkGroupError> {
+            let caller = self.env().caller();
+            let admin = self.get_admin()?;
+            ensure!(caller == admin, InkGroupError::Unauthorized {});
+            let old = self.code_hash();
+            // Not a call to another contract: the only failure is a code hash without code
+            self.env()
+                .set_code_hash(&new_code_hash)
+                .map_err(|_| InkGroupError::UnknownCodeHash {})?;
+            self.record_action(ActionKind::Upgraded);
+            self.touch();
+            self.env().emit_event(Upgraded {
+                old,
+                new: new_code_hash,
+            });
+            Ok(())
+        }
+
+        #[ink(message)]
+        /// Move members with their weights to a new group instantiated from `new_group_code_hash`
+        /// and return its address (only admin can).
+        pub fn spin_off(
+            &mut self,
+            members_to_move: Vec<AccountId>,
+            new_group_code_hash: Hash,
+        ) -> Result<AccountId, InkGroupError> {
+            let caller = self.env().caller();
+            let admin = self.get_admin()?;
+            ensure!(caller == admin, InkGroupError::Unauthorized {});
+            // Neither group can be left without members
+            ensure!(!members_to_move.is_empty(), InkGroupError::ZeroMembers {});
+            validate_unique_addresses(&members_to_move)?;
+            let mut moved = Vec::with_capacity(members_to_move.len());
+            for addr in members_to_move.iter() {
+                moved.push(self.get_member(*addr)?);
+            }
+            ensure!(
+                moved.len() < self.members.len(),
+                InkGroupError::ZeroMembers {}
+            );
+            self.update_members(vec![], members_to_move)?;
+            // The removal is reverted with the whole call if the instantiation fails. The new
+            // epoch salts the instantiation, so every spin off gets a new address.
+            let new_group = InkGroupSimpleRef::try_new(Some(admin), moved)
+                .code_hash(new_group_code_hash)
+                .endowment(0)
+                .salt_bytes(self.current_epoch().to_le_bytes())
+                .try_instantiate()??
+                .map_err(|ContractError::InkGroup(err)| err)?;
+            Ok(ink::ToAccountId::to_account_id(&new_group))
+        }
+
+        /// Record the current block as the last mutation of the group
+        fn touch(&mut self) {
+            let block = self.env().block_number();
+            self.last_mutated.set(&block);
+        }
+    }
+
+    impl InkGroup for InkGroupSimple {
+        #[ink(message)]
+        /// Return current admin.
+        fn get_admin(&self) -> Result<AccountId, InkGroupError> {
+            // Should always be some admin in case of error the logic of the contract is wrong
+            let admin = self.admin.get().ok_or(InkGroupError::LogicErr {})?;
+            Ok(admin)
+        }
+
+        #[ink(message)]
+        /// Return all members info.
+        fn get_members(&self) -> Result<Vec<Member>, InkGroupError> {
+            // Should always be some member in case of error the logic of the contract is
+            // wrong
+            if self.members.is_empty() {
+                return Err(InkGroupError::LogicErr {});
+            }
+            Ok(self.members.to_vec())
+        }
+
+        #[ink(message)]
+        /// Return member info searched by address.
+        fn get_member(&self, member: AccountId) -> Result<Member, InkGroupError> {
+            // Return error in case of the member is not found in the group
+            let founded_member = self
+                .members
+                .iter()
+                .cloned()
+                .find(|&memb| memb.addr == member)
+                .ok_or(InkGroupError::NoMember {})?;
+            Ok(founded_member)
+        }
+
+        #[ink(message)]
+        /// Return up to `limit` members whose address starts with `prefix`.
+        fn find_members_by_prefix(
+            &self,
+            prefix: Vec<u8>,
+            limit: u32,
+        ) -> Result<Vec<Member>, InkGroupError> {
+            // An address is 32 bytes long so a longer prefix can't match anything
+            ensure!(prefix.len() <= 32, InkGroupError::InvalidPrefix {});
+            let members = self
+                .members
+                .iter()
+                .filter(|member| AsRef::<[u8]>::as_ref(&member.addr).starts_with(&prefix))
+                .take(limit as usize)
+                .cloned()
+                .collect();
+            Ok(members)
+        }
+
+        #[ink(message)]
+        /// Return the total voting power.
+        fn get_total_weight(&self) -> u64 {
+            self.total_voting_power.get()
+        }
+
+        #[ink(message)]
+        /// Return the block number of the last successful mutation.
+        fn last_mutated_block(&self) -> u32 {
+            self.last_mutated.get().unwrap_or_default()
+        }
+
+        #[ink(message)]
+        /// Return the optional capabilities of the contract (only events).
+        fn supported_features(&self) -> u32 {
+            features::EVENTS
+        }
+
+        #[ink(message)]
+        /// Return the summed weight of the `n` highest weight members.
+        fn top_n_weight(&self, n: u32) -> u64 {
+            let n = (n as usize).min(self.members.len());
+            if n == 0 {
+                return 0;
+            }
+            let mut weights: Vec<u64> = self.members.iter().map(|member| member.weight).collect();
+            // Partial selection (no full sort): the n highest weights are moved before the n-th
+            weights.select_nth_unstable_by(n - 1, |a, b| b.cmp(a));
+            weights[..n].iter().sum()
+        }
+
+        #[ink(message)]
+        /// Return the membership epoch.
+        fn current_epoch(&self) -> u64 {
+            self.epoch.get().unwrap_or_default()
+        }
+
+        #[ink(message)]
+        /// Return if the members of the group are exactly `expected` in any order.
+        fn matches_members(&self, expected: Vec<Member>) -> bool {
+            if expected.len() != self.members.len() {
+                return false;
+            }
+            let mut expected = expected;
+            let mut members = self.members.clone();
+            members.sort_unstable();
+            expected.sort_unstable();
+            *members == expected
+        }
+
+        #[ink(message)]
+        /// Return if at least one member has a weight of at least `min`.
+        fn exists_member_with_weight(&self, min: u64) -> bool {
+            self.members.iter().any(|member| member.weight >= min)
+        }
+
+        #[ink(message)]
+        /// Return the members in `[start, end)` by storage order.
+        fn members_slice(&self, start: u32, end: u32) -> Result<Vec<Member>, InkGroupError> {
+            let (start, end) = (start as usize, end as usize);
+            ensure!(
+                start <= end && end <= self.members.len(),
+                InkGroupError::InvalidRange {}
+            );
+            Ok(self.members[start..end].to_vec())
+        }
+
+        #[ink(message)]
+        /// Return current admin, admin transfers are immediate so there is never a pending one.
+        fn admin_state(&self) -> Result<(AccountId, Option<AccountId>), InkGroupError> {
+            Ok((self.get_admin()?, None))
+        }
+
+        #[ink(message)]
+        /// Return the message ids with the selector of the core messages.
+        fn selectors(&self) -> Vec<(u32, [u8; 4])> {
+            selectors::CORE.to_vec()
+        }
+
+        #[ink(message)]
+        /// Return the number of members in each weight bucket (at most `MAX_BUCKET_BOUNDARIES`
+        /// boundaries).
+        fn weight_buckets(&self, boundaries: Vec<u64>) -> Result<Vec<u32>, InkGroupError> {
+            ensure!(
+                boundaries.len() <= MAX_BUCKET_BOUNDARIES
+                    && boundaries.windows(2).all(|pair| pair[0] < pair[1]),
+                InkGroupError::InvalidBoundaries {}
+            );
+            let mut buckets = vec![0u32; boundaries.len() + 1];
+            for member in self.members.iter() {
+                // Number of boundaries not above the weight, i.e. the index of its bucket
+                let bucket = boundaries.partition_point(|&boundary| boundary <= member.weight);
+                buckets[bucket] += 1;
+            }
+            Ok(buckets)
+        }
+
+        #[ink(message)]
+        /// Return the highest role held by the caller (there are no operators).
+        fn my_role(&self) -> Role {
+            let caller = self.env().caller();
+            if self.admin.get() == Some(caller) {
+                Role::Admin
+            } else if self.members.iter().any(|member| member.addr == caller) {
+                Role::Member
+            } else {
+                Role::None
+            }
+        }
+
+        #[ink(message)]
+        /// Return if the total voting power is above zero.
+        fn is_votable(&self) -> bool {
+            self.total_voting_power.get() > 0
+        }
+
+        #[ink(message)]
+        /// Return the members as merkle leaves sorted by address.
+        fn member_leaves(&self) -> Vec<[u8; 40]> {
+            let mut members = self.members.clone();
+            members.sort_unstable_by_key(|member| member.addr);
+            members.iter().map(member_leaf).collect()
+        }
+
+        #[ink(message)]
+        /// Return the merkle root of the member leaves.
+        fn members_root(&self) -> [u8; 32] {
+            merkle_root(&self.member_leaves())
+        }
+
+        #[ink(message)]
+        /// Return the minimum number of members reaching `percent` of the total voting power.
+        fn min_coalition_for(&self, percent: u32) -> Result<u32, InkGroupError> {
+            ensure!(percent <= 100, InkGroupError::InvalidPercent {});
+            // Without voting power only a zero threshold can be reached
+            ensure!(
+                self.total_voting_power.get() > 0 || percent == 0,
+                InkGroupError::QuorumUnreachable {}
+            );
+            // Compare `weight * 100 >= total * percent` to avoid rounding the threshold
+            let threshold = u128::from(self.total_voting_power.get()) * u128::from(percent);
+            let mut weights: Vec<u64> = self.members.iter().map(|member| member.weight).collect();
+            weights.sort_unstable_by(|a, b| b.cmp(a));
+            let mut coalition_power: u128 = 0;
+            let mut count: u32 = 0;
+            for weight in weights {
+                if coalition_power * 100 >= threshold {
+                    break;
+                }
+                coalition_power += u128::from(weight);
+                count += 1;
+            }
+            Ok(count)
+        }
+
+        #[ink(message)]
+        /// Check a candidate member list against the configured validation rules.
+        fn validate_member_set(&self, members: Vec<Member>) -> Result<(), InkGroupError> {
+            ensure!(!members.is_empty(), InkGroupError::ZeroMembers {});
+            self.validate_members(&members)?;
+            self.ensure_member_count_cap(members.len())?;
+            let total_power = members
+                .iter()
+                .try_fold(0u64, |total, member| total.checked_add(member.weight))
+                .ok_or(InkGroupError::WeightOverflow {})?;
+            self.ensure_total_weight_cap(total_power)?;
+            self.ensure_concentration_limit(&members, total_power)
+        }
+
+        #[ink(message)]
+        /// Return the digest of the admin, the sorted members, the epoch and the last mutation.
+        fn roster_digest(&self) -> Result<[u8; 32], InkGroupError> {
+            let mut members = self.members.to_vec();
+            members.sort_unstable_by_key(|member| member.addr);
+            let roster = (
+                self.get_admin()?,
+                members,
+                self.current_epoch(),
+                self.last_mutated_block(),
+            );
+            Ok(blake2x256(&scale::Encode::encode(&roster)))
+        }
+
+        #[ink(message)]
+        /// Return a page of members after the cursor with the next cursor and the current epoch.
+        fn sync_checkpoint(
+            &self,
+            cursor: Option<AccountId>,
+            limit: u32,
+        ) -> Result<SyncPage, InkGroupError> {
+            let start = match cursor {
+                Some(cursor) => {
+                    self.members
+                        .iter()
+                        .position(|member| member.addr == cursor)
+                        .ok_or(InkGroupError::NoMember {})?
+                        + 1
+                }
+                None => 0,
+            };
+            let end = start.saturating_add(limit as usize).min(self.members.len());
+            let members = self.members[start..end].to_vec();
+            let next_cursor = if end < self.members.len() {
+                members.last().map(|member| member.addr)
+            } else {
+                None
+            };
+            Ok(SyncPage {
+                members,
+                next_cursor,
+                epoch: self.current_epoch(),
+            })
+        }
+
+        #[ink(message)]
+        /// Return the addresses stored more than once.
+        fn find_duplicates(&self) -> Vec<AccountId> {
+            let mut addresses: Vec<AccountId> =
+                self.members.iter().map(|member| member.addr).collect();
+            addresses.sort_unstable();
+            let mut duplicates: Vec<AccountId> = addresses
+                .windows(2)
+                .filter(|pair| pair[0] == pair[1])
+                .map(|pair| pair[0])
+                .collect();
+            duplicates.dedup();
+            duplicates
+        }
+
+        #[ink(message)]
+        /// Return the members added or whose weight changed at or after `epoch`.
+        fn members_changed_since_epoch(&self, epoch: u64) -> Vec<Member> {
+            self.members
+                .iter()
+                .filter(|member| self.member_changed_at.get(member.addr).unwrap_or(0) >= epoch)
+                .cloned()
+                .collect()
+        }
+
+        #[ink(message)]
+        /// Return the raw Banzhaf index of each member (at most `MAX_PIVOTAL_MEMBERS` members).
+        fn pivotal_count(&self, percent: u32) -> Result<Vec<(AccountId, u32)>, InkGroupError> {
+            ensure!(percent <= 100, InkGroupError::InvalidPercent {});
+            ensure!(
+                self.members.len() <= MAX_PIVOTAL_MEMBERS,
+                InkGroupError::TooManyMembers {
+                    max: MAX_PIVOTAL_MEMBERS as u32
+                }
+            );
+            let threshold = u128::from(self.total_voting_power.get()) * u128::from(percent);
+            let reaches = |weight: u128| weight * 100 >= threshold;
+            let mut counts = vec![0u32; self.members.len()];
+            // Every subset of the members as a bit mask
+            for coalition in 0u32..(1 << self.members.len()) {
+                let coalition_power: u128 = self
+                    .members
+                    .iter()
+                    .enumerate()
+                    .filter(|(index, _)| coalition & (1 << index) != 0)
+                    .map(|(_, member)| u128::from(member.weight))
+                    .sum();
+                if reaches(coalition_power) {
+                    continue;
+                }
+                for (index, member) in self.members.iter().enumerate() {
+                    if coalition & (1 << index) == 0
+                        && reaches(coalition_power + u128::from(member.weight))
+                    {
+                        counts[index] += 1;
+                    }
+                }
+            }
+            Ok(self
+                .members
+                .iter()
+                .map(|member| member.addr)
+                .zip(counts)
+                .collect())
+        }
+
+        #[ink(message)]
+        /// Return the Gini coefficient of the weights in basis points (at most
+        /// `MAX_GINI_MEMBERS` members).
+        fn weight_gini_bps(&self) -> Result<u32, InkGroupError> {
+            ensure!(
+                self.members.len() <= MAX_GINI_MEMBERS,
+                InkGroupError::TooManyMembers {
+                    max: MAX_GINI_MEMBERS as u32
+                }
+            );
+            if self.total_voting_power.get() == 0 {
+                return Ok(0);
+            }
+            let mut weights: Vec<u64> = self.members.iter().map(|member| member.weight).collect();
+            weights.sort_unstable();
+            let count = weights.len() as u128;
+            let total = u128::from(self.total_voting_power.get());
+            // Σ i * x_i with 1-based ranks, can't overflow u128 for at most `MAX_GINI_MEMBERS`
+            let ranked_sum: u128 = weights
+                .iter()
+                .enumerate()
+                .map(|(index, &weight)| (index as u128 + 1) * u128::from(weight))
+                .sum();
+            // Non negative as the weights are sorted ascending
+            let numerator = (2 * ranked_sum)
+                .checked_sub((count + 1) * total)
+                .ok_or(InkGroupError::LogicErr {})?;
+            Ok((numerator * 10_000 / (count * total)) as u32)
+        }
+
+        #[ink(message)]
+        /// Return the admin, the balance of the contract and the total voting power.
+        fn treasury_view(&self) -> Result<(AccountId, u128, u64), InkGroupError> {
+            Ok((
+                self.get_admin()?,
+                self.env().balance(),
+                self.total_voting_power.get(),
+            ))
+        }
+
+        #[ink(message)]
+        /// Return the number of members and of members with a weight above zero.
+        fn membership_counts(&self) -> (u32, u32) {
+            let voting = self
+                .members
+                .iter()
+                .filter(|member| member.weight > 0)
+                .count();
+            (self.members.len() as u32, voting as u32)
+        }
+
+        #[ink(message)]
+        /// Return the lowest weight among the members.
+        fn min_weight(&self) -> Result<u64, InkGroupError> {
+            self.members
+                .iter()
+                .map(|member| member.weight)
+                .min()
+                .ok_or(InkGroupError::LogicErr {})
+        }
+
+        #[ink(message)]
+        /// Return the highest weight among the members.
+        fn max_weight(&self) -> Result<u64, InkGroupError> {
+            self.members
+                .iter()
+                .map(|member| member.weight)
+                .max()
+                .ok_or(InkGroupError::LogicErr {})
+        }
+
+        #[ink(message)]
+        /// Return each account with its weight (at most `MAX_LOOKUP_ACCOUNTS` accounts).
+        fn lookup_weights(
+            &self,
+            accounts: Vec<AccountId>,
+        ) -> Result<Vec<(AccountId, Option<u64>)>, InkGroupError> {
+            ensure!(
+                accounts.len() <= MAX_LOOKUP_ACCOUNTS,
+                InkGroupError::InputTooLong {
+                    max: MAX_LOOKUP_ACCOUNTS as u32
+                }
+            );
+            Ok(accounts
+                .into_iter()
+                .map(|account| {
+                    let weight = self
+                        .members
+                        .iter()
+                        .find(|member| member.addr == account)
+                        .map(|member| member.weight);
+                    (account, weight)
+                })
+                .collect())
+        }
+
+        #[ink(message)]
+        /// Return the status of the group.
+        fn health(&self) -> GroupHealth {
+            GroupHealth {
+                member_count: self.members.len() as u32,
+                total_weight: self.total_voting_power.get(),
+                admin: self.admin.get(),
+                last_mutated: self.last_mutated_block(),
+                epoch: self.current_epoch(),
+            }
+        }
+
+        #[ink(message)]
+        /// Check that the target weights of existing members respect the caps.
+        fn distribution_feasible(
+            &self,
+            target: Vec<(AccountId, u64)>,
+        ) -> Result<(), InkGroupError> {
+            ensure!(!target.is_empty(), InkGroupError::EmptyUpdate {});
+            let addresses: Vec<AccountId> = target.iter().map(|(addr, _)| *addr).collect();
+            validate_unique_addresses(&addresses)?;
+            let mut members = self.members.clone();
+            for (addr, weight) in target {
+                let member = members
+                    .iter_mut()
+                    .find(|member| member.addr == addr)
+                    .ok_or(InkGroupError::NoMember {})?;
+                self.ensure_member_weight_cap(weight)?;
+                member.weight = weight;
+            }
+            let total_power = members
+                .iter()
+                .try_fold(0u64, |total, member| total.checked_add(member.weight))
+                .ok_or(InkGroupError::WeightOverflow {})?;
+            self.ensure_total_weight_cap(total_power)?;
+            self.ensure_concentration_limit(&members, total_power)
+        }
+
+        #[ink(message)]
+        /// Return the weight the member must gain to reach `target_rank`.
+        fn weight_to_rank(
+            &self,
+            member: AccountId,
+            target_rank: u32,
+        ) -> Result<u64, InkGroupError> {
+            ensure!(target_rank > 0, InkGroupError::InvalidRange {});
+            let weight = self.get_member(member)?.weight;
+            let mut others: Vec<u64> = self
+                .members
+                .iter()
+                .filter(|other| other.addr != member)
+                .map(|other| other.weight)
+                .collect();
+            others.sort_unstable_by(|a, b| b.cmp(a));
+            // Reaching the weight of the other member at `target_rank` leaves fewer than
+            // `target_rank` members strictly above
+            Ok(others
+                .get(target_rank as usize - 1)
+                .map_or(0, |target| target.saturating_sub(weight)))
+        }
+
+        #[ink(message)]
+        /// Return the weight of a member and its share of the total voting power in basis
+        /// points.
+        fn member_weight_and_share(&self, addr: AccountId) -> Result<(u64, u32), InkGroupError> {
+            let weight = self.get_member(addr)?.weight;
+            let total = u128::from(self.total_voting_power.get());
+            if total == 0 {
+                return Ok((weight, 0));
+            }
+            // The weight of a member is at most the total, so the share fits in a u32
+            let bps = u128::from(weight)
+                .checked_mul(10_000)
+                .ok_or(InkGroupError::WeightOverflow {})?
+                / total;
+            Ok((
+                weight,
+                u32::try_from(bps).map_err(|_| InkGroupError::LogicErr {})?,
+            ))
+        }
+
+        #[ink(message)]
+        /// Return the total voting power and the number of members without the member.
+        fn impact_of_removing(&self, member: AccountId) -> Result<(u64, u32), InkGroupError> {
+            let weight = self.get_member(member)?.weight;
+            let mut new_total = self.total_voting_power;
+            new_total.sub_weight(weight)?;
+            Ok((new_total.get(), self.members.len() as u32 - 1))
+        }
+
+        #[ink(message)]
+        /// Return the members as CSV rows sorted by address.
+        fn export_csv(&self) -> Vec<u8> {
+            let mut members = self.members.clone();
+            members.sort_unstable_by_key(|member| member.addr);
+            let mut csv = Vec::new();
+            for member in members.iter() {
+                push_csv_row(&mut csv, member);
+            }
+            csv
+        }
+
+        #[ink(message)]
+        /// Return the code hash of the contract.
+        fn code_hash(&self) -> Hash {
+            // It only fails for an account that is not a contract, never for the contract itself
+            self.env().own_code_hash().unwrap_or_default()
+        }
+
+        #[ink(message)]
+        /// Return the median weight of the members (the lower middle one for an even count).
+        fn median_weight(&self) -> Result<u64, InkGroupError> {
+            ensure!(!self.members.is_empty(), InkGroupError::LogicErr {});
+            let mut weights: Vec<u64> = self.members.iter().map(|member| member.weight).collect();
+            let middle = (weights.len() - 1) / 2;
+            Ok(*weights.select_nth_unstable(middle).1)
+        }
+
+        #[ink(message)]
+        /// Return the `n` members with the highest weight with their rank and share.
+        fn leaderboard(&self, n: u32) -> Vec<(u32, AccountId, u64, u32)> {
+            let mut members = self.members.clone();
+            // Ties by address, like `committee`
+            members.sort_unstable_by(rank_order);
+            let total = u128::from(self.total_voting_power.get());
+            let mut rank = 0;
+            members
+                .iter()
+                .take(n as usize)
+                .enumerate()
+                .map(|(index, member)| {
+                    if index == 0 || members[index - 1].weight != member.weight {
+                        rank = index as u32 + 1;
+                    }
+                    // The weight of a member is at most the total, so the share fits in a u32,
+                    // and a zero total gives a zero share
+                    let bps = (u128::from(member.weight) * 10_000)
+                        .checked_div(total)
+                        .unwrap_or(0) as u32;
+                    (rank, member.addr, member.weight, bps)
+                })
+                .collect()
+        }
+
+        #[ink(message)]
+        /// Return how many more members can be added (`None` if uncapped).
+        fn remaining_capacity(&self) -> Option<u32> {
+            self.config()
+                .max_members
+                .map(|max| max.saturating_sub(self.members.len() as u32))
+        }
+
+        #[ink(message)]
+        /// Return whether both addresses are members of the group.
+        fn both_members(&self, a: AccountId, b: AccountId) -> bool {
+            let is_member = |addr| self.members.iter().any(|member| member.addr == addr);
+            is_member(a) && is_member(b)
+        }
+
+        #[ink(message)]
+        /// Return the weight of the admin as a member (0 if not a member).
+        fn admin_weight(&self) -> u64 {
+            self.admin
+                .get()
+                .and_then(|admin| self.get_member(admin).ok())
+                .map_or(0, |member| member.weight)
+        }
+
+        #[ink(message)]
+        /// Return the number of members and their weight in each non-empty power of ten tier.
+        fn tier_distribution(&self) -> Vec<(u64, u32, u64)> {
+            // Tier 0 holds the zero weights, tier `k + 1` the weights from `10^k` to `10^(k + 1) - 1`
+            let mut tiers = [(0u32, 0u64); 21];
+            for member in self.members.iter() {
+                let tier = match member.weight {
+                    0 => 0,
+                    weight => weight.ilog10() as usize + 1,
+                };
+                tiers[tier].0 += 1;
+                // The total voting power fits in a u64, so does the weight of any tier
+                tiers[tier].1 += member.weight;
+            }
+            tiers
+                .iter()
+                .enumerate()
+                .filter(|(_, (count, _))| *count > 0)
+                .map(|(tier, (count, weight))| {
+                    let tier_min = match tier {
+                        0 => 0,
+                        tier => 10u64.pow(tier as u32 - 1),
+                    };
+                    (tier_min, *count, *weight)
+                })
+                .collect()
+        }
+
+        #[ink(message)]
+        /// Return the addresses of the `k` members with the highest weight, ties by address.
+        fn committee(&self, k: u32) -> Vec<AccountId> {
+            let mut members = self.members.clone();
+            members.sort_unstable_by(rank_order);
+            members
+                .iter()
+                .take(k as usize)
+                .map(|member| member.addr)
+                .collect()
+        }
+
+        #[ink(message)]
+        /// Return whether the caller is a member with a weight of at least `min`.
+        fn caller_has_weight(&self, min: u64) -> bool {
+            self.get_member(self.env().caller())
+                .is_ok_and(|member| member.weight >= min)
+        }
+
+        #[ink(message)]
+        /// Return the hash of the admin, the sorted initial members and the configuration.
+        fn genesis_hash(&self) -> [u8; 32] {
+            self.genesis_hash.get().unwrap_or_default()
+        }
+
+        #[ink(message)]
+        /// Return an estimate of the storage footprint in bytes (members plus a fixed overhead).
+        fn storage_estimate(&self) -> u64 {
+            // A member is stored as its 32-byte address and 8-byte weight (4-byte if compact), its
+            // size in memory
+            let member_size = if self.members.is_compact() {
+                core::mem::size_of::<Member<u32>>() as u64
+            } else {
+                core::mem::size_of::<Member>() as u64
+            };
+            (self.members.len() as u64)
+                .saturating_mul(member_size)
+                .saturating_add(STORAGE_OVERHEAD_BYTES)
+        }
+
+        #[ink(message)]
+        /// Return whether members can currently be added or removed.
+        fn membership_mutable(&self) -> bool {
+            // The frozen membership is the only lifecycle flag of the contract
+            !self.membership_frozen
+        }
+
+        #[ink(message)]
+        /// Return the member at the median position by weight (lower middle, ties by address).
+        fn median_member(&self) -> Result<AccountId, InkGroupError> {
+            ensure!(!self.members.is_empty(), InkGroupError::LogicErr {});
+            let mut members = self.members.clone();
+            let middle = (members.len() - 1) / 2;
+            let (_, median, _) = members.select_nth_unstable_by(middle, |a, b| {
+                a.weight.cmp(&b.weight).then(a.addr.cmp(&b.addr))
+            });
+            Ok(median.addr)
+        }
+
+        #[ink(message)]
+        /// Return whether all the members together reach `percent` of the total voting power.
+        fn quorum_reachable(&self, percent: u32) -> Result<bool, InkGroupError> {
+            ensure!(percent <= 100, InkGroupError::InvalidPercent {});
+            // Every member is active: their combined weight is the total voting power
+            let active_power = u128::from(self.total_voting_power.get());
+            // Compare `active * 100 >= total * percent` to avoid rounding the threshold
+            let threshold = u128::from(self.total_voting_power.get()) * u128::from(percent);
+            // Without voting power only a zero threshold can be reached
+            Ok(percent == 0 || (active_power > 0 && active_power * 100 >= threshold))
+        }
+
+        #[ink(message)]
+        /// Return whether the caller is the pending admin, never as transfers are immediate.
+        fn am_i_pending_admin(&self) -> bool {
+            let caller = self.env().caller();
+            matches!(self.admin_state(), Ok((_, Some(pending))) if pending == caller)
+        }
+
+        #[ink(message)]
+        /// Change the admin (only current admin can).
+        fn update_admin(&mut self, new_admin: AccountId) -> Result<(), InkGroupError> {
+            let caller = self.env().caller();
+            let admin = self.get_admin()?;
+            ensure!(caller == admin, InkGroupError::Unauthorized {});
+            self.ensure_valid_new_admin(new_admin)?;
+            self.transfer_admin(admin, new_admin);
+            Ok(())
+        }
+
+        #[ink(message)]
+        /// Set the weight of a member and make it the admin (only admin can). A changed weight
+        /// also emits `MemberWeightChanged`.
+        fn promote_to_controller(
+            &mut self,
+            member: AccountId,
+            weight: u64,
+        ) -> Result<(), InkGroupError> {
+            let caller = self.env().caller();
+            let admin = self.get_admin()?;
+            ensure!(caller == admin, InkGroupError::Unauthorized {});
+            let old_weight = self.get_member(member)?.weight;
+            // Check the transfer before the weight update, so that nothing is applied if it fails
+            self.ensure_valid_new_admin(member)?;
+            self.update_members(
+                vec![Member {
+                    addr: member,
+                    weight,
+                }],
+                vec![],
+            )?;
+            if weight != old_weight {
+                self.env().emit_event(MemberWeightChanged {
+                    member,
+                    old_weight,
+                    new_weight: weight,
+                });
+            }
+            self.transfer_admin(admin, member);
+            Ok(())
+        }
+
+        #[ink(message)]
+        /// If an already existing address is entered, the voting power is updated. Remove is applied after add, so if an address is in both, it is removed.
+        /// Both lists are processed by address, whatever the order they are entered in.
+        fn update_members(
+            &mut self,
+            new_members: Vec<Member>,
+            remove_members: Vec<AccountId>,
+        ) -> Result<u64, InkGroupError> {
+            let caller = self.env().caller();
+            let admin = self.get_admin()?;
+            ensure!(caller == admin, InkGroupError::Unauthorized {});
+            // A call without members to add or remove is a misconfiguration, not a no-op
+            ensure!(
+                !new_members.is_empty() || !remove_members.is_empty(),
+                InkGroupError::EmptyUpdate {}
+            );
+            self.ensure_batch_size(new_members.len() + remove_members.len())?;
+            self.validate_members(&new_members)?;
+            // Process the batch by address, so that the same logical batch always emits the same
+            // events and leaves the same storage order
+            let mut new_members = new_members;
+            new_members.sort_unstable_by_key(|member| member.addr);
+            let mut remove_members = remove_members;
+            remove_members.sort_unstable();
+            if self.config().forbid_self_weight_change {
+                ensure!(
+                    new_members.iter().all(|member| member.addr != caller),
+                    InkGroupError::CannotModifyOwnWeight {}
+                );
+            }
+            if self.config().admin_must_not_be_member {
+                ensure!(
+                    new_members.iter().all(|member| member.addr != admin),
+                    InkGroupError::AdminCannotBeMember {}
+                );
+            }
+            // Compute the whole prospective state before touching the storage, so that a batch
+            // failing any check leaves the group exactly as it was
+            let mut members = self.members.clone();
+            let mut total_power = self.total_voting_power;
+            let reject_noop_updates = self.config().reject_noop_updates;
+            // The members are loaded in memory as a single vector: reserve the space for the new
+            // ones upfront so that pushing them doesn't reallocate more than once
+            members.reserve(new_members.len());
+            // Added (`None`) or updated (old weight) members, by address
+            let mut upserted = Vec::with_capacity(new_members.len());
+            // Members added or whose weight changes
+            let mut weight_changed = Vec::with_capacity(new_members.len());
+            // for every new member check if already exist in the group, in that case update the voting power
+            // otherwise add the member to the group
+            for member in new_members {
+                if let Some(index) = members
+                    .iter()
+                    .position(|&old_member| old_member.addr == member.addr)
+                {
+                    if reject_noop_updates {
+                        ensure!(
+                            members[index].weight != member.weight,
+                            InkGroupError::NoChange {}
+                        );
+                    }
+                    // first subtract the old vote weight from the total, then add the new one
+                    total_power.sub_weight(members[index].weight)?;
+                    total_power.add_weight(member.weight)?;
+                    if members[index].weight != member.weight {
+                        self.ensure_cooldown_elapsed(member.addr)?;
+                        weight_changed.push(member.addr);
+                    }
+                    upserted.push((member, Some(members[index].weight)));
+                    // last change the old vote weight of the member to the new
+                    members[index].weight = member.weight;
+                } else {
+                    self.ensure_membership_unfrozen()?;
+                    // add the new member and then add the vote weight to the total
+                    members.push(member);
+                    total_power.add_weight(member.weight)?;
+                    weight_changed.push(member.addr);
+                    upserted.push((member, None));
+                }
+            }
+            // for each member to be removed check that it actually already exists within the group
+            // and in this case first subtract the weight of the vote from the total and then
+            // delete the member otherwise do nothing
+            let mut removed = Vec::new();
+            for member in remove_members {
+                if let Some(index) = members
+                    .iter()
+                    .position(|&old_member| old_member.addr == member)
+                {
+                    self.ensure_membership_unfrozen()?;
+                    ensure!(
+                        !self.locked_members.contains(member),
+                        InkGroupError::MemberLocked { member }
+                    );
+                    total_power.sub_weight(members[index].weight)?;
+                    removed.push(members.remove(index));
+                }
+            }
+            self.ensure_member_count_cap(members.len())?;
+            self.ensure_total_weight_cap(total_power.get())?;
+            self.ensure_concentration_limit(&members, total_power.get())?;
+
+            // Removing absent addresses and re-sending the current weights is a no-op: the epoch
+            // and the last mutation only advance when the roster changes
+            let changed = !weight_changed.is_empty() || !removed.is_empty();
+
+            // Every check passed: commit the new state and emit the events
+            self.members = members;
+            self.total_voting_power = total_power;
+            for addr in weight_changed {
+                self.mark_changed(addr);
+            }
+            for (member, old_weight) in upserted {
+                if let Some(old_weight) = old_weight {
+                    // Emit event that the member was updated
+                    self.env().emit_event(MemberUpdate {
+                        member: member.addr,
+                    });
+                    self.record_change(
+                        ChangeKind::Updated,
+                        member.addr,
+                        i128::from(member.weight) - i128::from(old_weight),
+                    );
+                } else {
+                    // Emit the event that the member was added
+                    self.env().emit_event(MemberAddition {
+                        member: member.addr,
+                        weight: member.weight,
+                    });
+                    self.record_change(ChangeKind::Added, member.addr, member.weight.into());
+                }
+            }
+            for removed_member in removed {
+                // Emit the event that the member was removed
+                self.env().emit_event(MemberRemoval {
+                    member: removed_member.addr,
+                    weight: removed_member.weight,
+                });
+                self.record_change(
+                    ChangeKind::Removed,
+                    removed_member.addr,
+                    -i128::from(removed_member.weight),
+                );
+                self.member_roles.remove(removed_member.addr);
+                self.member_changed_at.remove(removed_member.addr);
+                self.member_changed_block.remove(removed_member.addr);
+                self.notify_removed(removed_member)?;
+            }
+            if changed {
+                self.update_threshold_met();
+                self.bump_epoch();
+                self.record_action(ActionKind::MembersUpdated);
+                self.touch();
+            }
+
+            Ok(self.total_voting_power.get())
+        }
+
+        #[ink(message)]
+        /// Remove every member with a weight below `threshold` (only admin can).
+        fn prune_below_weight(&mut self, threshold: u64) -> Result<u32, InkGroupError> {
+            let caller = self.env().caller();
+            let admin = self.get_admin()?;
+            ensure!(caller == admin, InkGroupError::Unauthorized {});
+            let (removed, kept): (Vec<Member>, Vec<Member>) = self
+                .members
+                .iter()
+                .partition(|member| member.weight < threshold);
+            // The group can't be left without members
+            ensure!(!kept.is_empty(), InkGroupError::ZeroMembers {});
+            if let Some(locked) = removed
+                .iter()
+                .find(|member| self.locked_members.contains(member.addr))
+            {
+                return Err(InkGroupError::MemberLocked {
+                    member: locked.addr,
+                });
+            }
+            if removed.is_empty() {
+                return Ok(0);
+            }
+            self.ensure_membership_unfrozen()?;
+            let mut total_power = self.total_voting_power;
+            for member in &removed {
+                total_power.sub_weight(member.weight)?;
+            }
+            self.ensure_concentration_limit(&kept, total_power.get())?;
+            *self.members = kept;
+            self.total_voting_power = total_power;
+            for member in &removed {
+                // Emit the event that the member was removed
+                self.env().emit_event(MemberRemoval {
+                    member: member.addr,
+                    weight: member.weight,
+                });
+                self.record_change(ChangeKind::Removed, member.addr, -i128::from(member.weight));
+                self.member_roles.remove(member.addr);
+                self.member_changed_at.remove(member.addr);
+                self.member_changed_block.remove(member.addr);
+                self.notify_removed(*member)?;
+            }
+            self.update_threshold_met();
+            self.bump_epoch();
+            self.record_action(ActionKind::MembersUpdated);
+            self.touch();
+            Ok(removed.len() as u32)
+        }
+
+        #[ink(message)]
+        /// Update the weight of an existing member (only admin can).
+        fn update_member_weight(
+            &mut self,
+            member: AccountId,
+            weight: u64,
+        ) -> Result<(), InkGroupError> {
+            let caller = self.env().caller();
+            let admin = self.get_admin()?;
+            ensure!(caller == admin, InkGroupError::Unauthorized {});
+            // Unlike `update_members` this never adds a member
+            self.get_member(member)?;
+            self.update_members(
+                vec![Member {
+                    addr: member,
+                    weight,
+                }],
+                vec![],
+            )?;
+            Ok(())
+        }
+
+        #[ink(message)]
+        /// Remove a member from the group (only admin can).
+        fn remove_member(&mut self, member: AccountId) -> Result<(), InkGroupError> {
+            self.update_members(vec![], vec![member])?;
+            Ok(())
+        }
+
+        #[ink(message)]
+        /// Remove the member at `index` and return it (only admin can).
+        fn remove_member_at(&mut self, index: u32) -> Result<Member, InkGroupError> {
+            let caller = self.env().caller();
+            let admin = self.get_admin()?;
+            ensure!(caller == admin, InkGroupError::Unauthorized {});
+            let removed_member = *self
+                .members
+                .get(index as usize)
+                .ok_or(InkGroupError::NoMember {})?;
+            self.remove_member(removed_member.addr)?;
+            Ok(removed_member)
+        }
+
+        #[ink(message)]
+        /// Remove the members from the group, rejecting duplicate and absent addresses (only
+        /// admin can).
+        fn remove_members(&mut self, members: Vec<AccountId>) -> Result<(), InkGroupError> {
+            let caller = self.env().caller();
+            let admin = self.get_admin()?;
+            ensure!(caller == admin, InkGroupError::Unauthorized {});
+            validate_unique_addresses(&members)?;
+            for member in members.iter() {
+                self.get_member(*member)?;
+            }
+            self.update_members(vec![], members)?;
+            Ok(())
+        }
+
+        #[ink(message)]
+        /// Apply signed deltas to the weight of existing members, all or nothing (only admin
+        /// can).
+        fn adjust_weights(&mut self, deltas: Vec<(AccountId, i64)>) -> Result<(), InkGroupError> {
+            let caller = self.env().caller();
+            let admin = self.get_admin()?;
+            ensure!(caller == admin, InkGroupError::Unauthorized {});
+            ensure!(!deltas.is_empty(), InkGroupError::EmptyUpdate {});
+            self.ensure_batch_size(deltas.len())?;
+            let addresses: Vec<AccountId> = deltas.iter().map(|(addr, _)| *addr).collect();
+            validate_unique_addresses(&addresses)?;
+            if self.config().forbid_self_weight_change {
+                ensure!(
+                    !addresses.contains(&caller),
+                    InkGroupError::CannotModifyOwnWeight {}
+                );
+            }
+            // Compute the new weights before touching the storage
+            let mut members = self.members.clone();
+            let mut changed = Vec::new();
+            for (addr, delta) in deltas {
+                let member = members
+                    .iter_mut()
+                    .find(|member| member.addr == addr)
+                    .ok_or(InkGroupError::NoMember {})?;
+                let new_weight = if delta < 0 {
+                    member
+                        .weight
+                        .checked_sub(delta.unsigned_abs())
+                        .ok_or(InkGroupError::WeightUnderflow {})?
+                } else {
+                    member
+                        .weight
+                        .checked_add(delta.unsigned_abs())
+                        .ok_or(InkGroupError::WeightOverflow {})?
+                };
+                self.ensure_member_weight_cap(new_weight)?;
+                if new_weight != member.weight {
+                    self.ensure_cooldown_elapsed(addr)?;
+                    changed.push((addr, member.weight, new_weight));
+                    member.weight = new_weight;
+                }
+            }
+            // Recompute the total once
+            let mut total_power = TotalWeight::default();
+            for member in members.iter() {
+                total_power.add_weight(member.weight)?;
+            }
+            self.ensure_total_weight_cap(total_power.get())?;
+            self.ensure_concentration_limit(&members, total_power.get())?;
+
+            self.members = members;
+            self.total_voting_power = total_power;
+            for (member, old_weight, new_weight) in changed {
+                self.mark_changed(member);
+                self.env().emit_event(MemberWeightChanged {
+                    member,
+                    old_weight,
+                    new_weight,
+                });
+                self.record_change(
+                    ChangeKind::Updated,
+                    member,
+                    i128::from(new_weight) - i128::from(old_weight),
+                );
+            }
+            self.update_threshold_met();
+            self.bump_epoch();
+            self.record_action(ActionKind::MembersUpdated);
+            self.touch();
+            Ok(())
+        }
+
+        #[ink(message)]
+        /// Remove the caller from the group (only if enabled by the configuration).
+        fn leave(&mut self) -> Result<(), InkGroupError> {
+            ensure!(
+                self.config().allow_self_leave,
+                InkGroupError::Unauthorized {}
+            );
+            let caller = self.env().caller();
+            let index = self
+                .members
+                .iter()
+                .position(|member| member.addr == caller)
+                .ok_or(InkGroupError::NoMember {})?;
+            // The group can't be left without members
+            ensure!(self.members.len() > 1, InkGroupError::ZeroMembers {});
+            self.ensure_membership_unfrozen()?;
+            ensure!(
+                !self.locked_members.contains(caller),
+                InkGroupError::MemberLocked { member: caller }
+            );
+            let mut members = self.members.to_vec();
+            let removed_member = members.remove(index);
+            let mut total_power = self.total_voting_power;
+            total_power.sub_weight(removed_member.weight)?;
+            self.ensure_concentration_limit(&members, total_power.get())?;
+            *self.members = members;
+            self.total_voting_power = total_power;
+            // Emit the event that the member was removed
+            self.env().emit_event(MemberRemoval {
+                member: removed_member.addr,
+                weight: removed_member.weight,
+            });
+            self.record_change(
+                ChangeKind::Removed,
+                removed_member.addr,
+                -i128::from(removed_member.weight),
+            );
+            self.member_roles.remove(removed_member.addr);
+            self.member_changed_at.remove(removed_member.addr);
+            self.member_changed_block.remove(removed_member.addr);
+            self.notify_removed(removed_member)?;
+            self.update_threshold_met();
+            self.bump_epoch();
+            self.touch();
+            Ok(())
+        }
+        #[ink(message)]
+        /// Make the members exactly `desired`, touching only the differing ones (only admin can).
+        fn sync_to(&mut self, desired: Vec<Member>) -> Result<UpdateReport, InkGroupError> {
+            let caller = self.env().caller();
+            let admin = self.get_admin()?;
+            ensure!(caller == admin, InkGroupError::Unauthorized {});
+            ensure!(!desired.is_empty(), InkGroupError::ZeroMembers {});
+            validate_unique_members(&desired)?;
+            let mut report = UpdateReport::default();
+            let mut upserts = Vec::new();
+            for member in desired.iter() {
+                match self.get_member(member.addr) {
+                    Ok(old_member) if old_member.weight == member.weight => {}
+                    Ok(_) => {
+                        report.updated += 1;
+                        upserts.push(*member);
+                    }
+                    Err(_) => {
+                        report.added += 1;
+                        upserts.push(*member);
+                    }
+                }
+            }
+            let removals: Vec<AccountId> = self
+                .members
+                .iter()
+                .filter(|member| desired.iter().all(|desired| desired.addr != member.addr))
+                .map(|member| member.addr)
+                .collect();
+            report.removed = removals.len() as u32;
+            if !upserts.is_empty() || !removals.is_empty() {
+                self.update_members(upserts, removals)?;
+            }
+            Ok(report)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use ink::env::test::{self, EmittedEvent};
+
+        type Event = <InkGroupSimple as ::ink::reflect::ContractEventBase>::Type;
+
+        // Integration test setup
+
+        fn default_accounts() -> test::DefaultAccounts<Environment> {
+            ink::env::test::default_accounts::<Environment>()
+        }
+
+        fn set_caller(sender: AccountId) {
+            ink::env::test::set_caller::<Environment>(sender);
+        }
+
+        fn build_contract() -> InkGroupSimple {
+            let accounts = default_accounts();
+
+            let alice_member = Member {
+                addr: accounts.alice,
+                weight: 1,
+            };
+            let bob_member = Member {
+                addr: accounts.bob,
+                weight: 1,
+            };
+
+            let members = vec![alice_member, bob_member];
+
+            set_caller(alice_member.addr);
+
+            InkGroupSimple::try_new(None, members).unwrap()
+        }
+
+        fn decode_events(emittend_events: Vec<EmittedEvent>) -> Vec<Event> {
+            emittend_events
+                .into_iter()
+                .map(|event| {
+                    <Event as scale::Decode>::decode(&mut &event.data[..]).expect("invalid data")
+                })
+                .collect()
+        }
+
+        #[ink::test]
+        /// The default constructor does its job.
+        fn construction_works() {
+            let accounts = default_accounts();
+            let alice_member = Member {
+                addr: accounts.alice,
+                weight: 1,
+            };
+            let bob_member = Member {
+                addr: accounts.bob,
+                weight: 1,
+            };
+            let charlie_member = Member {
+                addr: accounts.charlie,
+                weight: 1,
+            };
+            let members = vec![alice_member, bob_member];
+            let contract = build_contract();
+
+            let emittend_events: Vec<EmittedEvent> = ink::env::test::recorded_events().collect();
+            let decoded_events = decode_events(emittend_events);
+            if let Event::MemberAddition(MemberAddition { member, weight }) = decoded_events[0] {
+                assert_eq!(member, accounts.alice);
+                assert_eq!(weight, 1);
+            } else {
+                panic!("encountered unexpected event kind: expected a MemberAddition event")
+            }
+
+            assert_eq!(contract.members.len(), 2);
+            assert_eq!(contract.admin.get().unwrap(), accounts.alice);
+            assert!(contract.members.iter().eq(members.iter()));
+            assert!(contract.members.contains(&alice_member));
+            assert!(!contract.members.contains(&charlie_member));
+        }
+
+        #[ink::test]
+        /// Members can be weighted with `u128` for token-weighted groups
+        fn u128_member_works() {
+            let accounts = default_accounts();
+            let token_member: Member<u128> = Member {
+                addr: accounts.alice,
+                weight: u128::from(u64::MAX) + 1,
+            };
+            let simple_member = Member {
+                addr: accounts.alice,
+                weight: u64::MAX,
+            };
+            // The weight type is part of the encoding: 32 bytes address + 16 or 8 bytes weight
+            let encoded = scale::Encode::encode(&token_member);
+            assert_eq!(encoded.len(), 48);
+            assert_eq!(scale::Encode::encode(&simple_member).len(), 40);
+            let decoded = <Member<u128> as scale::Decode>::decode(&mut &encoded[..]).unwrap();
+            assert_eq!(decoded, token_member);
+        }
+
+        #[ink::test]
+        /// Get the current admin of the group
+        fn get_admin_works() {
+            let accounts = default_accounts();
+            let contract = build_contract();
+            let response = InkGroupSimple::get_admin(&contract).unwrap();
+            assert_eq!(response, accounts.alice);
+        }
+
+        #[ink::test]
+        /// Get the members of the group
+        fn get_members_works() {
+            let accounts = default_accounts();
+            let alice_member = Member {
+                addr: accounts.alice,
+                weight: 1,
+            };
+            let bob_member = Member {
+                addr: accounts.bob,
+                weight: 1,
+            };
+            let charlie_member = Member {
+                addr: accounts.charlie,
+                weight: 1,
+            };
+            let members = vec![alice_member, bob_member];
+            let contract = build_contract();
+            let response = InkGroupSimple::get_members(&contract).unwrap();
+            assert_eq!(response, members);
+            assert!(!response.contains(&charlie_member));
+        }
+
+        #[ink::test]
+        /// Get member info searched by address
+        fn get_member_works() {
+            let accounts = default_accounts();
+            let alice_member = Member {
+                addr: accounts.alice,
+                weight: 1,
+            };
+            let contract = build_contract();
+            let response = InkGroupSimple::get_member(&contract, accounts.alice).unwrap();
+            assert_eq!(response, alice_member);
+            let err_response = InkGroupSimple::get_member(&contract, accounts.eve).unwrap_err();
+            assert_eq!(err_response, InkGroupError::NoMember {});
+        }
+
+        #[ink::test]
+        /// Find members by address prefix
+        fn find_members_by_prefix_works() {
+            let accounts = default_accounts();
+            let contract = build_contract();
+            // Default accounts are `[0x01; 32]` (alice) and `[0x02; 32]` (bob)
+            let response =
+                InkGroupSimple::find_members_by_prefix(&contract, vec![0x01, 0x01], 10).unwrap();
+            assert_eq!(response.len(), 1);
+            assert_eq!(response[0].addr, accounts.alice);
+            // An empty prefix matches every member up to the limit
+            let response = InkGroupSimple::find_members_by_prefix(&contract, vec![], 10).unwrap();
+            assert_eq!(response.len(), 2);
+            let response = InkGroupSimple::find_members_by_prefix(&contract, vec![], 1).unwrap();
+            assert_eq!(response.len(), 1);
+            let response =
+                InkGroupSimple::find_members_by_prefix(&contract, vec![0x01, 0x02], 10).unwrap();
+            assert!(response.is_empty());
+            let err_response =
+                InkGroupSimple::find_members_by_prefix(&contract, vec![0x01; 33], 10).unwrap_err();
+            assert_eq!(err_response, InkGroupError::InvalidPrefix {});
+        }
+
+        #[ink::test]
+        /// Get total voting power
+        fn get_total_weight_works() {
+            let contract = build_contract();
+            let response = InkGroupSimple::get_total_weight(&contract);
+            assert_eq!(response, 2);
+        }
+
+        #[ink::test]
+        /// Get the block of the last mutation
+        fn last_mutated_block_works() {
+            let accounts = default_accounts();
+            let mut contract = build_contract();
+            assert_eq!(InkGroupSimple::last_mutated_block(&contract), 0);
+            // Read-only calls don't advance the last mutation
+            test::advance_block::<Environment>();
+            InkGroupSimple::get_admin(&contract).unwrap();
+            InkGroupSimple::get_members(&contract).unwrap();
+            InkGroupSimple::get_member(&contract, accounts.alice).unwrap();
+            InkGroupSimple::get_total_weight(&contract);
+            assert_eq!(InkGroupSimple::last_mutated_block(&contract), 0);
+            // Every mutation advances it to the current block
+            let charlie_member = Member {
+                addr: accounts.charlie,
+                weight: 1,
+            };
+            InkGroupSimple::update_members(&mut contract, vec![charlie_member], vec![]).unwrap();
+            assert_eq!(InkGroupSimple::last_mutated_block(&contract), 1);
+            // Including the configuration changes
+            test::advance_block::<Environment>();
+            InkGroupSimple::set_max_batch_size(&mut contract, 10).unwrap();
+            assert_eq!(InkGroupSimple::last_mutated_block(&contract), 2);
+            test::advance_block::<Environment>();
+            InkGroupSimple::update_admin(&mut contract, accounts.bob).unwrap();
+            assert_eq!(InkGroupSimple::last_mutated_block(&contract), 3);
+            // A failed mutation doesn't advance it
+            test::advance_block::<Environment>();
+            InkGroupSimple::update_admin(&mut contract, accounts.bob).unwrap_err();
+            assert_eq!(InkGroupSimple::last_mutated_block(&contract), 3);
+        }
+
+        #[cfg(feature = "cross-contract")]
+        #[ink::test]
+        /// Sync weights from token balances (the off-chain environment can't call the token, so
+        /// only the authorization and the balance capping are covered here)
+        fn sync_weights_from_token_works() {
+            let accounts = default_accounts();
+            let mut contract = build_contract();
+            set_caller(accounts.bob);
+            let err_response =
+                InkGroupSimple::sync_weights_from_token(&mut contract, accounts.frank).unwrap_err();
+            assert_eq!(err_response, InkGroupError::Unauthorized {});
+            let err_response =
+                InkGroupSimple::sync_weights_capped(&mut contract, accounts.frank, 10).unwrap_err();
+            assert_eq!(err_response, InkGroupError::Unauthorized {});
+            assert_eq!(balance_to_weight(42), 42);
+            assert_eq!(balance_to_weight(u128::from(u64::MAX)), u64::MAX);
+            assert_eq!(balance_to_weight(u128::from(u64::MAX) + 1), u64::MAX);
+        }
+
+        #[ink::test]
+        /// Get the optional capabilities
+        fn supported_features_works() {
+            let contract = build_contract();
+            let response = InkGroupSimple::supported_features(&contract);
+            assert_eq!(response, features::EVENTS);
+            assert_eq!(response & features::VOTING, 0);
+            assert_eq!(response & features::DELEGATION, 0);
+        }
+
+        #[ink::test]
+        /// Set the remove hook (the off-chain environment can't call the hook, so only the
+        /// configuration and the removal without hook are covered here)
+        fn set_remove_hook_works() {
+            let accounts = default_accounts();
+            let mut contract = build_contract();
+            assert_eq!(InkGroupSimple::get_remove_hook(&contract), None);
+            set_caller(accounts.bob);
+            let err_response =
+                InkGroupSimple::set_remove_hook(&mut contract, Some(accounts.frank)).unwrap_err();
+            assert_eq!(err_response, InkGroupError::Unauthorized {});
+            set_caller(accounts.alice);
+            InkGroupSimple::set_remove_hook(&mut contract, Some(accounts.frank)).unwrap();
+            assert_eq!(
+                InkGroupSimple::get_remove_hook(&contract),
+                Some(accounts.frank)
+            );
+            InkGroupSimple::set_remove_hook(&mut contract, None).unwrap();
+            assert_eq!(InkGroupSimple::get_remove_hook(&contract), None);
+            InkGroupSimple::update_members(&mut contract, vec![], vec![accounts.bob]).unwrap();
+            assert_eq!(InkGroupSimple::get_members(&contract).unwrap().len(), 1);
+        }
+
+        #[ink::test]
+        /// Add a large batch of members at once
+        fn update_members_large_batch_works() {
+            let mut contract = build_contract();
+            let new_members: Vec<Member> = (10..110u8)
+                .map(|byte| Member {
+                    addr: AccountId::from([byte; 32]),
+                    weight: u64::from(byte),
+                })
+                .collect();
+            InkGroupSimple::update_members(&mut contract, new_members.clone(), vec![]).unwrap();
+            let members = InkGroupSimple::get_members(&contract).unwrap();
+            assert_eq!(members.len(), 102);
+            assert_eq!(members[2..], new_members[..]);
+            let expected_total: u64 = 2 + (10..110u64).sum::<u64>();
+            assert_eq!(InkGroupSimple::get_total_weight(&contract), expected_total);
+            InkGroupSimple::verify_invariants(&contract).unwrap();
+        }
+
+        #[ink::test]
+        /// Verify the storage invariants
+        fn verify_invariants_works() {
+            let mut contract = build_contract();
+            InkGroupSimple::verify_invariants(&contract).unwrap();
+            // Corrupt the total voting power
+            contract.total_voting_power.add_weight(1).unwrap();
+            let err_response = InkGroupSimple::verify_invariants(&contract).unwrap_err();
+            assert_eq!(err_response, InkGroupError::LogicErr {});
+        }
+
+        /// Deterministic pseudo-random generator (linear congruential) for the stress tests
+        struct Lcg(u64);
+
+        impl Lcg {
+            fn next(&mut self, bound: u64) -> u64 {
+                self.0 = self
+                    .0
+                    .wrapping_mul(6364136223846793005)
+                    .wrapping_add(1442695040888963407);
+                (self.0 >> 33) % bound
+            }
+        }
+
+        #[ink::test]
+        /// Interleave many randomized `update_members` and `update_admin` calls and check the
+        /// invariants after each of them
+        fn randomized_updates_keep_invariants() {
+            let accounts = default_accounts();
+            let pool = [
+                accounts.alice,
+                accounts.bob,
+                accounts.charlie,
+                accounts.django,
+                accounts.eve,
+                accounts.frank,
+            ];
+            let mut rng = Lcg(42);
+            let mut contract = build_contract();
+            for _ in 0..200 {
+                let admin = InkGroupSimple::get_admin(&contract).unwrap();
+                // Most of the calls come from the admin, the others must be rejected
+                let caller = if rng.next(4) == 0 {
+                    pool[rng.next(6) as usize]
+                } else {
+                    admin
+                };
+                set_caller(caller);
+                let result = if rng.next(5) == 0 {
+                    InkGroupSimple::update_admin(&mut contract, pool[rng.next(6) as usize])
+                        .map(|_| InkGroupSimple::get_total_weight(&contract))
+                } else {
+                    let new_members = (0..rng.next(4))
+                        .map(|_| Member {
+                            addr: pool[rng.next(6) as usize],
+                            weight: rng.next(100),
+                        })
+                        .collect();
+                    let remove_members = (0..rng.next(3))
+                        .map(|_| pool[rng.next(6) as usize])
+                        .collect();
+                    InkGroupSimple::update_members(&mut contract, new_members, remove_members)
+                };
+                match result {
+                    Ok(total) => {
+                        assert_eq!(caller, admin);
+                        assert_eq!(total, InkGroupSimple::get_total_weight(&contract));
+                    }
+                    Err(InkGroupError::Unauthorized {}) => assert_ne!(caller, admin),
+                    Err(InkGroupError::DuplicateMember { .. })
+                    | Err(InkGroupError::EmptyUpdate {}) => {}
+                    Err(err) => panic!("unexpected error: {:?}", err),
+                }
+                InkGroupSimple::verify_invariants(&contract).unwrap();
+            }
+        }
+
+        #[ink::test]
+        /// Update admin
+        fn update_admin_works() {
+            let accounts = default_accounts();
+            let mut contract = build_contract();
+            set_caller(accounts.bob);
+            let err_response =
+                InkGroupSimple::update_admin(&mut contract, accounts.bob).unwrap_err();
+            assert_eq!(err_response, InkGroupError::Unauthorized {});
+            set_caller(accounts.alice);
+            InkGroupSimple::update_admin(&mut contract, accounts.bob).unwrap();
+            assert_eq!(contract.admin.get().unwrap(), accounts.bob);
+            let emittend_events: Vec<EmittedEvent> = ink::env::test::recorded_events().collect();
+            let decoded_events = decode_events(emittend_events);
+            if let Event::AdminUpdate(AdminUpdate {
+                old_admin,
+                new_admin,
+            }) = decoded_events[2]
+            {
+                assert_eq!(old_admin, accounts.alice);
+                assert_eq!(new_admin, accounts.bob);
+            } else {
+                panic!("encountered unexpected event kind: expected a MemberAddition event")
+            }
+        }
+
+        #[ink::test]
+        /// Update members
+        fn update_members_works() {
+            let accounts = default_accounts();
+            let mut contract = build_contract();
+            set_caller(accounts.bob);
+            let err_response =
+                InkGroupSimple::update_admin(&mut contract, accounts.bob).unwrap_err();
+            assert_eq!(err_response, InkGroupError::Unauthorized {});
+            set_caller(accounts.alice);
+            let update_alice = Member {
+                addr: accounts.alice,
+                weight: 2,
+            };
+            let bob_member = Member {
+                addr: accounts.bob,
+                weight: 1,
+            };
+            let charlie_member = Member {
+                addr: accounts.charlie,
+                weight: 1,
+            };
+            let new_total =
+                InkGroupSimple::update_members(&mut contract, vec![update_alice], vec![]).unwrap();
+            let result = InkGroupSimple::get_member(&contract, accounts.alice).unwrap();
+            let total_voting_power = InkGroupSimple::get_total_weight(&contract);
+            assert_eq!(result.weight, 2);
+            assert_eq!(total_voting_power, 3);
+            assert_eq!(new_total, total_voting_power);
+            let new_total =
+                InkGroupSimple::update_members(&mut contract, vec![charlie_member], vec![])
+                    .unwrap();
+            let result = InkGroupSimple::get_members(&contract).unwrap();
+            let total_voting_power = InkGroupSimple::get_total_weight(&contract);
+            assert_eq!(result.len(), 3);
+            assert_eq!(total_voting_power, 4);
+            assert_eq!(new_total, total_voting_power);
+            let new_total =
+                InkGroupSimple::update_members(&mut contract, vec![], vec![accounts.alice])
+                    .unwrap();
+            let result = InkGroupSimple::get_members(&contract).unwrap();
+            let total_voting_power = InkGroupSimple::get_total_weight(&contract);
+            assert_eq!(result.len(), 2);
+            assert_eq!(total_voting_power, 2);
+            assert_eq!(new_total, total_voting_power);
+            let err_response =
+                InkGroupSimple::update_members(&mut contract, vec![bob_member, bob_member], vec![])
+                    .unwrap_err();
+            assert_eq!(
+                err_response,
+                InkGroupError::DuplicateMember {
+                    member: accounts.bob
+                }
+            );
+            // Also when the duplicates are not adjacent
+            let err_response = InkGroupSimple::update_members(
+                &mut contract,
+                vec![bob_member, charlie_member, bob_member],
+                vec![],
+            )
+            .unwrap_err();
+            assert_eq!(
+                err_response,
+                InkGroupError::DuplicateMember {
+                    member: accounts.bob
+                }
+            );
+            let err_response =
+                InkGroupSimple::update_members(&mut contract, vec![], vec![]).unwrap_err();
+            assert_eq!(err_response, InkGroupError::EmptyUpdate {});
+            let emittend_events: Vec<EmittedEvent> = ink::env::test::recorded_events().collect();
+            let decoded_events = decode_events(emittend_events);
+            if let Event::MemberUpdate(MemberUpdate { member }) = decoded_events[2] {
+                assert_eq!(member, accounts.alice);
+            } else {
+                panic!("encountered unexpected event kind: expected a MemberAddition event")
+            }
+            if let Event::MemberAddition(MemberAddition { member, weight }) = decoded_events[3] {
+                assert_eq!(member, accounts.charlie);
+                assert_eq!(weight, 1);
+            } else {
+                panic!("encountered unexpected event kind: expected a MemberAddition event")
+            }
+            if let Event::MemberRemoval(MemberRemoval { member, weight }) = decoded_events[4] {
+                assert_eq!(member, accounts.alice);
+                // The weight alice had when removed
+                assert_eq!(weight, 2);
+            } else {
+                panic!("encountered unexpected event kind: expected a MemberAddition event")
+            }
+        }
+
+        #[ink::test]
+        /// Get the summed weight of the top n members
+        fn top_n_weight_works() {
+            let accounts = default_accounts();
+            let mut contract = build_contract();
+            let charlie_member = Member {
+                addr: accounts.charlie,
+                weight: 5,
+            };
+            let django_member = Member {
+                addr: accounts.django,
+                weight: 3,
+            };
+            InkGroupSimple::update_members(
+                &mut contract,
+                vec![charlie_member, django_member],
+                vec![],
+            )
+            .unwrap();
+            assert_eq!(InkGroupSimple::top_n_weight(&contract, 0), 0);
+            assert_eq!(InkGroupSimple::top_n_weight(&contract, 1), 5);
+            assert_eq!(InkGroupSimple::top_n_weight(&contract, 2), 8);
+            assert_eq!(InkGroupSimple::top_n_weight(&contract, 4), 10);
+            assert_eq!(InkGroupSimple::top_n_weight(&contract, 100), 10);
+        }
+
+        #[ink::test]
+        /// The zero address can't be the admin
+        fn zero_admin_is_rejected() {
+            let accounts = default_accounts();
+            let zero_address = AccountId::from([0; 32]);
+            let members = vec![Member {
+                addr: accounts.alice,
+                weight: 1,
+            }];
+            set_caller(accounts.alice);
+            let err_response = InkGroupSimple::try_new(Some(zero_address), members).unwrap_err();
+            assert_eq!(
+                err_response,
+                ContractError::InkGroup(InkGroupError::InvalidAdmin {})
+            );
+            let mut contract = build_contract();
+            let err_response =
+                InkGroupSimple::update_admin(&mut contract, zero_address).unwrap_err();
+            assert_eq!(err_response, InkGroupError::InvalidAdmin {});
+            assert_eq!(contract.admin.get().unwrap(), accounts.alice);
+        }
+
+        #[ink::test]
+        /// Get the membership epoch
+        fn current_epoch_works() {
+            let accounts = default_accounts();
+            let mut contract = build_contract();
+            assert_eq!(InkGroupSimple::current_epoch(&contract), 0);
+            // Read-only calls and admin changes don't bump the epoch
+            InkGroupSimple::get_members(&contract).unwrap();
+            InkGroupSimple::get_total_weight(&contract);
+            InkGroupSimple::update_admin(&mut contract, accounts.alice).unwrap();
+            assert_eq!(InkGroupSimple::current_epoch(&contract), 0);
+            // Every membership change bumps it by exactly one
+            let charlie_member = Member {
+                addr: accounts.charlie,
+                weight: 1,
+            };
+            InkGroupSimple::update_members(&mut contract, vec![charlie_member], vec![accounts.bob])
+                .unwrap();
+            assert_eq!(InkGroupSimple::current_epoch(&contract), 1);
+            InkGroupSimple::update_members(&mut contract, vec![], vec![accounts.charlie]).unwrap();
+            assert_eq!(InkGroupSimple::current_epoch(&contract), 2);
+            // A failed change doesn't bump it
+            InkGroupSimple::update_members(&mut contract, vec![], vec![]).unwrap_err();
+            assert_eq!(InkGroupSimple::current_epoch(&contract), 2);
+            // Neither does a call leaving the roster unchanged, nor the last mutation
+            let last_mutated = InkGroupSimple::last_mutated_block(&contract);
+            test::advance_block::<Environment>();
+            InkGroupSimple::remove_member(&mut contract, accounts.charlie).unwrap();
+            let alice_member = InkGroupSimple::get_member(&contract, accounts.alice).unwrap();
+            InkGroupSimple::update_members(&mut contract, vec![alice_member], vec![]).unwrap();
+            assert_eq!(InkGroupSimple::current_epoch(&contract), 2);
+            assert_eq!(InkGroupSimple::last_mutated_block(&contract), last_mutated);
+        }
+
+        #[ink::test]
+        /// Compare the members with an expected set
+        fn matches_members_works() {
+            let accounts = default_accounts();
+            let contract = build_contract();
+            let alice_member = Member {
+                addr: accounts.alice,
+                weight: 1,
+            };
+            let bob_member = Member {
+                addr: accounts.bob,
+                weight: 1,
+            };
+            assert!(InkGroupSimple::matches_members(
+                &contract,
+                vec![bob_member, alice_member]
+            ));
+            let heavier_bob = Member {
+                addr: accounts.bob,
+                weight: 2,
+            };
+            assert!(!InkGroupSimple::matches_members(
+                &contract,
+                vec![alice_member, heavier_bob]
+            ));
+            assert!(!InkGroupSimple::matches_members(
+                &contract,
+                vec![alice_member]
+            ));
+            assert!(!InkGroupSimple::matches_members(
+                &contract,
+                vec![alice_member, alice_member]
+            ));
+        }
+
+        #[ink::test]
+        /// Check if a member is heavy enough
+        fn exists_member_with_weight_works() {
+            let accounts = default_accounts();
+            let mut contract = build_contract();
+            let charlie_member = Member {
+                addr: accounts.charlie,
+                weight: 5,
+            };
+            InkGroupSimple::update_members(&mut contract, vec![charlie_member], vec![]).unwrap();
+            assert!(InkGroupSimple::exists_member_with_weight(&contract, 5));
+            assert!(!InkGroupSimple::exists_member_with_weight(&contract, 6));
+        }
+
+        #[ink::test]
+        /// Remove the members below a weight
+        fn prune_below_weight_works() {
+            let accounts = default_accounts();
+            let mut contract = build_contract();
+            let charlie_member = Member {
+                addr: accounts.charlie,
+                weight: 5,
+            };
+            let django_member = Member {
+                addr: accounts.django,
+                weight: 3,
+            };
+            InkGroupSimple::update_members(
+                &mut contract,
+                vec![charlie_member, django_member],
+                vec![],
+            )
+            .unwrap();
+            set_caller(accounts.bob);
+            let err_response = InkGroupSimple::prune_below_weight(&mut contract, 2).unwrap_err();
+            assert_eq!(err_response, InkGroupError::Unauthorized {});
+            set_caller(accounts.alice);
+            // The group can't be emptied
+            let err_response = InkGroupSimple::prune_below_weight(&mut contract, 10).unwrap_err();
+            assert_eq!(err_response, InkGroupError::ZeroMembers {});
+            assert_eq!(InkGroupSimple::get_members(&contract).unwrap().len(), 4);
+            assert_eq!(InkGroupSimple::prune_below_weight(&mut contract, 1), Ok(0));
+            assert_eq!(InkGroupSimple::prune_below_weight(&mut contract, 4), Ok(3));
+            assert_eq!(
+                InkGroupSimple::get_members(&contract).unwrap(),
+                vec![charlie_member]
+            );
+            assert_eq!(InkGroupSimple::get_total_weight(&contract), 5);
+            let emittend_events: Vec<EmittedEvent> = ink::env::test::recorded_events().collect();
+            let decoded_events = decode_events(emittend_events);
+            assert_eq!(decoded_events.len(), 7);
+            if let Event::MemberRemoval(MemberRemoval { member, weight }) = decoded_events[6] {
+                assert_eq!(member, accounts.django);
+                assert_eq!(weight, 3);
+            } else {
+                panic!("encountered unexpected event kind: expected a MemberRemoval event")
+            }
+        }
+
+        #[ink::test]
+        /// Get a range of members
+        fn members_slice_works() {
+            let accounts = default_accounts();
+            let contract = build_contract();
+            let response = InkGroupSimple::members_slice(&contract, 1, 2).unwrap();
+            assert_eq!(response.len(), 1);
+            assert_eq!(response[0].addr, accounts.bob);
+            assert_eq!(
+                InkGroupSimple::members_slice(&contract, 0, 2)
+                    .unwrap()
+                    .len(),
+                2
+            );
+            assert!(InkGroupSimple::members_slice(&contract, 2, 2)
+                .unwrap()
+                .is_empty());
+            assert_eq!(
+                InkGroupSimple::members_slice(&contract, 2, 1).unwrap_err(),
+                InkGroupError::InvalidRange {}
+            );
+            assert_eq!(
+                InkGroupSimple::members_slice(&contract, 0, 3).unwrap_err(),
+                InkGroupError::InvalidRange {}
+            );
+        }
+
+        #[ink::test]
+        /// Get the current and pending admin
+        fn admin_state_works() {
+            let accounts = default_accounts();
+            let mut contract = build_contract();
+            assert_eq!(
+                InkGroupSimple::admin_state(&contract),
+                Ok((accounts.alice, None))
+            );
+            InkGroupSimple::update_admin(&mut contract, accounts.bob).unwrap();
+            assert_eq!(
+                InkGroupSimple::admin_state(&contract),
+                Ok((accounts.bob, None))
+            );
+        }
+
+        #[ink::test]
+        /// The total voting power can't exceed the configured cap
+        fn max_total_weight_works() {
+            let accounts = default_accounts();
+            let config = GroupConfig {
+                max_total_weight: Some(3),
+                ..Default::default()
+            };
+            let alice_member = Member {
+                addr: accounts.alice,
+                weight: 2,
+            };
+            let bob_member = Member {
+                addr: accounts.bob,
+                weight: 2,
+            };
+            set_caller(accounts.alice);
+            let err_response =
+                InkGroupSimple::try_new_with_config(None, vec![alice_member, bob_member], config)
+                    .unwrap_err();
+            assert_eq!(
+                err_response,
+                ContractError::InkGroup(InkGroupError::TotalWeightCapExceeded { cap: 3 })
+            );
+            let bob_member = Member {
+                addr: accounts.bob,
+                weight: 1,
+            };
+            // At cap is accepted
+            let mut contract =
+                InkGroupSimple::try_new_with_config(None, vec![alice_member, bob_member], config)
+                    .unwrap();
+            assert_eq!(InkGroupSimple::get_total_weight(&contract), 3);
+            let charlie_member = Member {
+                addr: accounts.charlie,
+                weight: 1,
+            };
+            let err_response =
+                InkGroupSimple::update_members(&mut contract, vec![charlie_member], vec![])
+                    .unwrap_err();
+            assert_eq!(
+                err_response,
+                InkGroupError::TotalWeightCapExceeded { cap: 3 }
+            );
+        }
+
+        #[ink::test]
+        /// Get the core selectors, checked against the selectors dispatched by the contract
+        fn selectors_works() {
+            use ink::reflect::DispatchableMessageInfo;
+
+            macro_rules! dispatched {
+                ( $selector:expr ) => {
+                    <InkGroupSimple as DispatchableMessageInfo<
+                                                                { u32::from_be_bytes($selector) },
+                                                            >>::LABEL
+                };
+            }
+
+            let contract = build_contract();
+            let response = InkGroupSimple::selectors(&contract);
+            assert_eq!(response, selectors::CORE.to_vec());
+            assert_eq!(
+                dispatched!(selectors::GET_ADMIN_SELECTOR),
+                "InkGroup::get_admin"
+            );
+            assert_eq!(
+                dispatched!(selectors::GET_MEMBERS_SELECTOR),
+                "InkGroup::get_members"
+            );
+            assert_eq!(
+                dispatched!(selectors::GET_MEMBER_SELECTOR),
+                "InkGroup::get_member"
+            );
+            assert_eq!(
+                dispatched!(selectors::GET_TOTAL_WEIGHT_SELECTOR),
+                "InkGroup::get_total_weight"
+            );
+            assert_eq!(
+                dispatched!(selectors::UPDATE_ADMIN_SELECTOR),
+                "InkGroup::update_admin"
+            );
+            assert_eq!(
+                dispatched!(selectors::UPDATE_MEMBERS_SELECTOR),
+                "InkGroup::update_members"
+            );
+        }
+
+        #[ink::test]
+        /// A failing update leaves the group untouched
+        fn update_members_is_atomic() {
+            let accounts = default_accounts();
+            let config = GroupConfig {
+                max_total_weight: Some(4),
+                ..Default::default()
+            };
+            let alice_member = Member {
+                addr: accounts.alice,
+                weight: 1,
+            };
+            let bob_member = Member {
+                addr: accounts.bob,
+                weight: 1,
+            };
+            set_caller(accounts.alice);
+            let mut contract =
+                InkGroupSimple::try_new_with_config(None, vec![alice_member, bob_member], config)
+                    .unwrap();
+            test::advance_block::<Environment>();
+            let emitted_events = test::recorded_events().count();
+            // The update of bob and the addition of charlie fit the cap, django exceeds it
+            let new_members = vec![
+                Member {
+                    addr: accounts.bob,
+                    weight: 2,
+                },
+                Member {
+                    addr: accounts.charlie,
+                    weight: 1,
+                },
+                Member {
+                    addr: accounts.django,
+                    weight: 1,
+                },
+            ];
+            let err_response =
+                InkGroupSimple::update_members(&mut contract, new_members, vec![]).unwrap_err();
+            assert_eq!(
+                err_response,
+                InkGroupError::TotalWeightCapExceeded { cap: 4 }
+            );
+            assert_eq!(
+                InkGroupSimple::get_members(&contract).unwrap(),
+                vec![alice_member, bob_member]
+            );
+            assert_eq!(InkGroupSimple::get_total_weight(&contract), 2);
+            assert_eq!(InkGroupSimple::current_epoch(&contract), 0);
+            assert_eq!(InkGroupSimple::last_mutated_block(&contract), 0);
+            assert_eq!(test::recorded_events().count(), emitted_events);
+        }
+
+        #[ink::test]
+        /// Update the weight of a single member
+        fn update_member_weight_works() {
+            let accounts = default_accounts();
+            let mut contract = build_contract();
+            InkGroupSimple::update_member_weight(&mut contract, accounts.bob, 5).unwrap();
+            assert_eq!(
+                InkGroupSimple::get_member(&contract, accounts.bob),
+                Ok(Member {
+                    addr: accounts.bob,
+                    weight: 5
+                })
+            );
+            assert_eq!(InkGroupSimple::get_total_weight(&contract), 6);
+            // Only the admin can
+            set_caller(accounts.bob);
+            let err_response =
+                InkGroupSimple::update_member_weight(&mut contract, accounts.bob, 1).unwrap_err();
+            assert_eq!(err_response, InkGroupError::Unauthorized {});
+        }
+
+        #[ink::test]
+        /// Remove a single member
+        fn remove_member_works() {
+            let accounts = default_accounts();
+            let mut contract = build_contract();
+            InkGroupSimple::remove_member(&mut contract, accounts.bob).unwrap();
+            assert_eq!(
+                InkGroupSimple::get_members(&contract).unwrap(),
+                vec![Member {
+                    addr: accounts.alice,
+                    weight: 1
+                }]
+            );
+            assert_eq!(InkGroupSimple::get_total_weight(&contract), 1);
+            // Only the admin can
+            set_caller(accounts.bob);
+            let err_response =
+                InkGroupSimple::remove_member(&mut contract, accounts.alice).unwrap_err();
+            assert_eq!(err_response, InkGroupError::Unauthorized {});
+        }
+
+        #[ink::test]
+        /// Strictness of the membership setters for an address not in the group: only
+        /// `update_member_weight` errors, the remove paths are lenient and `update_members`
+        /// adds it
+        fn absent_member_strictness_works() {
+            let accounts = default_accounts();
+            let original_members = vec![
+                Member {
+                    addr: accounts.alice,
+                    weight: 1,
+                },
+                Member {
+                    addr: accounts.bob,
+                    weight: 1,
+                },
+            ];
+
+            let mut contract = build_contract();
+            let err_response =
+                InkGroupSimple::update_member_weight(&mut contract, accounts.charlie, 3)
+                    .unwrap_err();
+            assert_eq!(err_response, InkGroupError::NoMember {});
+            assert_eq!(
+                InkGroupSimple::get_members(&contract).unwrap(),
+                original_members
+            );
+            assert_eq!(InkGroupSimple::get_total_weight(&contract), 2);
+
+            // The lenient removals are no-ops: not even the epoch, the last mutation or the
+            // admin actions change
+            let mut contract = build_contract();
+            let epoch = InkGroupSimple::current_epoch(&contract);
+            let last_mutated = InkGroupSimple::last_mutated_block(&contract);
+            let actions = InkGroupSimple::admin_actions(&contract, 1);
+            test::advance_block::<Environment>();
+            InkGroupSimple::remove_member(&mut contract, accounts.charlie).unwrap();
+            assert_eq!(
+                InkGroupSimple::get_members(&contract).unwrap(),
+                original_members
+            );
+            assert_eq!(InkGroupSimple::get_total_weight(&contract), 2);
+            assert_eq!(InkGroupSimple::current_epoch(&contract), epoch);
+            assert_eq!(InkGroupSimple::last_mutated_block(&contract), last_mutated);
+            assert_eq!(InkGroupSimple::admin_actions(&contract, 1), actions);
+
+            let mut contract = build_contract();
+            let epoch = InkGroupSimple::current_epoch(&contract);
+            let last_mutated = InkGroupSimple::last_mutated_block(&contract);
+            let actions = InkGroupSimple::admin_actions(&contract, 1);
+            test::advance_block::<Environment>();
+            InkGroupSimple::update_members(&mut contract, vec![], vec![accounts.charlie]).unwrap();
+            assert_eq!(
+                InkGroupSimple::get_members(&contract).unwrap(),
+                original_members
+            );
+            assert_eq!(InkGroupSimple::get_total_weight(&contract), 2);
+            assert_eq!(InkGroupSimple::current_epoch(&contract), epoch);
+            assert_eq!(InkGroupSimple::last_mutated_block(&contract), last_mutated);
+            assert_eq!(InkGroupSimple::admin_actions(&contract, 1), actions);
+
+            let mut contract = build_contract();
+            let charlie_member = Member {
+                addr: accounts.charlie,
+                weight: 3,
+            };
+            InkGroupSimple::update_members(&mut contract, vec![charlie_member], vec![]).unwrap();
+            assert_eq!(
+                InkGroupSimple::get_member(&contract, accounts.charlie),
+                Ok(charlie_member)
+            );
+            assert_eq!(InkGroupSimple::get_total_weight(&contract), 5);
+        }
+
+        #[ink::test]
+        /// Count the members by weight bucket
+        fn weight_buckets_works() {
+            let mut contract = build_contract();
+            let new_members: Vec<Member> = [2, 10, 11, 100, 101, 500]
+                .iter()
+                .enumerate()
+                .map(|(index, &weight)| Member {
+                    addr: AccountId::from([index as u8 + 10; 32]),
+                    weight,
+                })
+                .collect();
+            InkGroupSimple::update_members(&mut contract, new_members, vec![]).unwrap();
+            let response = InkGroupSimple::weight_buckets(&contract, vec![2, 11, 101]).unwrap();
+            assert_eq!(response, vec![2, 2, 2, 2]);
+            let response = InkGroupSimple::weight_buckets(&contract, vec![]).unwrap();
+            assert_eq!(response, vec![8]);
+            let response = InkGroupSimple::weight_buckets(&contract, vec![1000]).unwrap();
+            assert_eq!(response, vec![8, 0]);
+            // Not strictly ascending
+            let err_response = InkGroupSimple::weight_buckets(&contract, vec![11, 2]).unwrap_err();
+            assert_eq!(err_response, InkGroupError::InvalidBoundaries {});
+            let err_response = InkGroupSimple::weight_buckets(&contract, vec![2, 2]).unwrap_err();
+            assert_eq!(err_response, InkGroupError::InvalidBoundaries {});
+            // Too many boundaries
+            let boundaries = (0..=MAX_BUCKET_BOUNDARIES as u64).collect();
+            let err_response = InkGroupSimple::weight_buckets(&contract, boundaries).unwrap_err();
+            assert_eq!(err_response, InkGroupError::InvalidBoundaries {});
+        }
+
+        #[ink::test]
+        /// Get the role of the caller
+        fn my_role_works() {
+            let accounts = default_accounts();
+            // Alice is the admin and a member
+            let mut contract = build_contract();
+            assert_eq!(InkGroupSimple::my_role(&contract), Role::Admin);
+            set_caller(accounts.bob);
+            assert_eq!(InkGroupSimple::my_role(&contract), Role::Member);
+            set_caller(accounts.charlie);
+            assert_eq!(InkGroupSimple::my_role(&contract), Role::None);
+            // A non member admin
+            set_caller(accounts.alice);
+            InkGroupSimple::update_admin(&mut contract, accounts.charlie).unwrap();
+            set_caller(accounts.charlie);
+            assert_eq!(InkGroupSimple::my_role(&contract), Role::Admin);
+            set_caller(accounts.alice);
+            assert_eq!(InkGroupSimple::my_role(&contract), Role::Member);
+        }
+
+        #[ink::test]
+        /// Remove a member by index
+        fn remove_member_at_works() {
+            let accounts = default_accounts();
+            let mut contract = build_contract();
+            let response = InkGroupSimple::remove_member_at(&mut contract, 0).unwrap();
+            assert_eq!(
+                response,
+                Member {
+                    addr: accounts.alice,
+                    weight: 1
+                }
+            );
+            assert_eq!(
+                InkGroupSimple::get_members(&contract).unwrap(),
+                vec![Member {
+                    addr: accounts.bob,
+                    weight: 1
+                }]
+            );
+            assert_eq!(InkGroupSimple::get_total_weight(&contract), 1);
+            let emitted_events = test::recorded_events().collect::<Vec<_>>();
+            let decoded_events = decode_events(emitted_events);
+            if let Event::MemberRemoval(MemberRemoval { member, weight }) =
+                decoded_events.last().unwrap()
+            {
+                assert_eq!(*member, accounts.alice);
+                assert_eq!(*weight, 1);
+            } else {
+                panic!("encountered unexpected event kind: expected a MemberRemoval event")
+            }
+            // Out of range
+            let err_response = InkGroupSimple::remove_member_at(&mut contract, 1).unwrap_err();
+            assert_eq!(err_response, InkGroupError::NoMember {});
+            // Only the admin can
+            set_caller(accounts.bob);
+            let err_response = InkGroupSimple::remove_member_at(&mut contract, 0).unwrap_err();
+            assert_eq!(err_response, InkGroupError::Unauthorized {});
+        }
+
+        #[ink::test]
+        /// A group of zero weight members is not votable
+        fn is_votable_works() {
+            let accounts = default_accounts();
+            let mut contract = build_contract();
+            assert!(InkGroupSimple::is_votable(&contract));
+            let zero_weight_members = vec![
+                Member {
+                    addr: accounts.alice,
+                    weight: 0,
+                },
+                Member {
+                    addr: accounts.bob,
+                    weight: 0,
+                },
+            ];
+            InkGroupSimple::update_members(&mut contract, zero_weight_members, vec![]).unwrap();
+            assert_eq!(InkGroupSimple::get_total_weight(&contract), 0);
+            assert!(!InkGroupSimple::is_votable(&contract));
+        }
+
+        #[ink::test]
+        /// Update the cap on the total voting power
+        fn set_total_weight_cap_works() {
+            let accounts = default_accounts();
+            let mut contract = build_contract();
+            // Below the current total of 2
+            let err_response =
+                InkGroupSimple::set_total_weight_cap(&mut contract, Some(1)).unwrap_err();
+            assert_eq!(
+                err_response,
+                InkGroupError::TotalWeightCapExceeded { cap: 1 }
+            );
+            InkGroupSimple::set_total_weight_cap(&mut contract, Some(2)).unwrap();
+            let charlie_member = Member {
+                addr: accounts.charlie,
+                weight: 1,
+            };
+            let err_response =
+                InkGroupSimple::update_members(&mut contract, vec![charlie_member], vec![])
+                    .unwrap_err();
+            assert_eq!(
+                err_response,
+                InkGroupError::TotalWeightCapExceeded { cap: 2 }
+            );
+            // Raise the cap
+            InkGroupSimple::set_total_weight_cap(&mut contract, Some(3)).unwrap();
+            InkGroupSimple::update_members(&mut contract, vec![charlie_member], vec![]).unwrap();
+            assert_eq!(InkGroupSimple::get_total_weight(&contract), 3);
+            let emitted_events = test::recorded_events().collect::<Vec<_>>();
+            let decoded_events = decode_events(emitted_events);
+            if let Event::WeightCapUpdated(WeightCapUpdated { old_cap, new_cap }) =
+                decoded_events[decoded_events.len() - 2]
+            {
+                assert_eq!(old_cap, Some(2));
+                assert_eq!(new_cap, Some(3));
+            } else {
+                panic!("encountered unexpected event kind: expected a WeightCapUpdated event")
+            }
+            // Remove the cap
+            InkGroupSimple::set_total_weight_cap(&mut contract, None).unwrap();
+            assert_eq!(contract.config().max_total_weight, None);
+            // Below the activation threshold, which could never be reached
+            let patch = GroupConfigPatch {
+                activation_threshold: Some(Some(5)),
+                ..Default::default()
+            };
+            InkGroupSimple::update_config(&mut contract, patch).unwrap();
+            let err_response =
+                InkGroupSimple::set_total_weight_cap(&mut contract, Some(4)).unwrap_err();
+            assert_eq!(err_response, InkGroupError::InvalidConfig {});
+            assert_eq!(contract.config().max_total_weight, None);
+            // Only the admin can
+            set_caller(accounts.bob);
+            let err_response =
+                InkGroupSimple::set_total_weight_cap(&mut contract, Some(10)).unwrap_err();
+            assert_eq!(err_response, InkGroupError::Unauthorized {});
+        }
+
+        #[ink::test]
+        /// Get the member leaves and their merkle root
+        fn member_leaves_works() {
+            use ink::env::hash::{Blake2x256, CryptoHash};
+
+            fn hash(input: &[u8]) -> [u8; 32] {
+                let mut output = [0u8; 32];
+                Blake2x256::hash(input, &mut output);
+                output
+            }
+
+            let accounts = default_accounts();
+            let mut contract = build_contract();
+            // Inserted before alice and bob by address
+            let zero_member = Member {
+                addr: AccountId::from([0; 32]),
+                weight: 0x0102,
+            };
+            InkGroupSimple::update_members(&mut contract, vec![zero_member], vec![]).unwrap();
+            let leaves = InkGroupSimple::member_leaves(&contract);
+            assert_eq!(leaves.len(), 3);
+            let mut zero_leaf = [0u8; 40];
+            zero_leaf[38..].copy_from_slice(&[0x01, 0x02]);
+            assert_eq!(leaves[0], zero_leaf);
+            assert_eq!(leaves[1][..32], [1u8; 32]);
+            assert_eq!(leaves[1][32..], 1u64.to_be_bytes());
+            assert_eq!(leaves[2][..32], *AsRef::<[u8]>::as_ref(&accounts.bob));
+            // The odd last leaf is carried to the root level
+            let left = hash(&[hash(&leaves[0]), hash(&leaves[1])].concat());
+            let root = hash(&[left, hash(&leaves[2])].concat());
+            assert_eq!(InkGroupSimple::members_root(&contract), root);
+            // The root doesn't depend on the insertion order
+            InkGroupSimple::update_members(&mut contract, vec![], vec![accounts.alice]).unwrap();
+            let alice_member = Member {
+                addr: accounts.alice,
+                weight: 1,
+            };
+            InkGroupSimple::update_members(&mut contract, vec![alice_member], vec![]).unwrap();
+            assert_eq!(InkGroupSimple::members_root(&contract), root);
+        }
+
+        #[ink::test]
+        /// A member weight can't exceed the configured cap, at construction or later
+        fn max_member_weight_works() {
+            let accounts = default_accounts();
+            let config = GroupConfig {
+                max_member_weight: Some(2),
+                ..Default::default()
+            };
+            let alice_member = Member {
+                addr: accounts.alice,
+                weight: 2,
+            };
+            let bob_member = Member {
+                addr: accounts.bob,
+                weight: 3,
+            };
+            set_caller(accounts.alice);
+            let err_response =
+                InkGroupSimple::try_new_with_config(None, vec![alice_member, bob_member], config)
+                    .unwrap_err();
+            assert_eq!(
+                err_response,
+                ContractError::InkGroup(InkGroupError::WeightCapExceeded { cap: 2 })
+            );
+            // At cap is accepted
+            let mut contract =
+                InkGroupSimple::try_new_with_config(None, vec![alice_member], config).unwrap();
+            let err_response =
+                InkGroupSimple::update_members(&mut contract, vec![bob_member], vec![])
+                    .unwrap_err();
+            assert_eq!(err_response, InkGroupError::WeightCapExceeded { cap: 2 });
+            let err_response =
+                InkGroupSimple::update_member_weight(&mut contract, accounts.alice, 3).unwrap_err();
+            assert_eq!(err_response, InkGroupError::WeightCapExceeded { cap: 2 });
+            assert_eq!(
+                InkGroupSimple::get_members(&contract).unwrap(),
+                vec![alice_member]
+            );
+        }
+
+        #[ink::test]
+        /// Remove members strictly
+        fn remove_members_works() {
+            let accounts = default_accounts();
+            let mut contract = build_contract();
+            // Duplicate address
+            let err_response =
+                InkGroupSimple::remove_members(&mut contract, vec![accounts.bob, accounts.bob])
+                    .unwrap_err();
+            assert_eq!(
+                err_response,
+                InkGroupError::DuplicateMember {
+                    member: accounts.bob
+                }
+            );
+            // Absent address
+            let err_response =
+                InkGroupSimple::remove_members(&mut contract, vec![accounts.bob, accounts.charlie])
+                    .unwrap_err();
+            assert_eq!(err_response, InkGroupError::NoMember {});
+            // Nothing removed on failure
+            assert_eq!(InkGroupSimple::get_members(&contract).unwrap().len(), 2);
+            let err_response = InkGroupSimple::remove_members(&mut contract, vec![]).unwrap_err();
+            assert_eq!(err_response, InkGroupError::EmptyUpdate {});
+            // The lenient path ignores the duplicate
+            InkGroupSimple::update_members(&mut contract, vec![], vec![accounts.bob, accounts.bob])
+                .unwrap();
+            assert_eq!(InkGroupSimple::get_members(&contract).unwrap().len(), 1);
+            InkGroupSimple::update_members(
+                &mut contract,
+                vec![Member {
+                    addr: accounts.bob,
+                    weight: 1,
+                }],
+                vec![],
+            )
+            .unwrap();
+            InkGroupSimple::remove_members(&mut contract, vec![accounts.bob]).unwrap();
+            assert_eq!(
+                InkGroupSimple::get_members(&contract).unwrap(),
+                vec![Member {
+                    addr: accounts.alice,
+                    weight: 1
+                }]
+            );
+            // Only the admin can
+            set_caller(accounts.bob);
+            let err_response =
+                InkGroupSimple::remove_members(&mut contract, vec![accounts.alice]).unwrap_err();
+            assert_eq!(err_response, InkGroupError::Unauthorized {});
+        }
+
+        #[ink::test]
+        /// Get the smallest coalition reaching a threshold
+        fn min_coalition_for_works() {
+            let accounts = default_accounts();
+            // Uniform weights: alice and bob with 1
+            let mut contract = build_contract();
+            assert_eq!(InkGroupSimple::min_coalition_for(&contract, 0), Ok(0));
+            assert_eq!(InkGroupSimple::min_coalition_for(&contract, 50), Ok(1));
+            assert_eq!(InkGroupSimple::min_coalition_for(&contract, 51), Ok(2));
+            assert_eq!(InkGroupSimple::min_coalition_for(&contract, 100), Ok(2));
+            let err_response = InkGroupSimple::min_coalition_for(&contract, 101).unwrap_err();
+            assert_eq!(err_response, InkGroupError::InvalidPercent {});
+            // Skewed weights: charlie 7, alice and bob 1 (total 9)
+            let charlie_member = Member {
+                addr: accounts.charlie,
+                weight: 7,
+            };
+            InkGroupSimple::update_members(&mut contract, vec![charlie_member], vec![]).unwrap();
+            assert_eq!(InkGroupSimple::min_coalition_for(&contract, 77), Ok(1));
+            assert_eq!(InkGroupSimple::min_coalition_for(&contract, 78), Ok(2));
+            assert_eq!(InkGroupSimple::min_coalition_for(&contract, 100), Ok(3));
+            // Zero total voting power
+            let zero_weight_members = [accounts.alice, accounts.bob, accounts.charlie]
+                .iter()
+                .map(|&addr| Member { addr, weight: 0 })
+                .collect();
+            InkGroupSimple::update_members(&mut contract, zero_weight_members, vec![]).unwrap();
+            let err_response = InkGroupSimple::min_coalition_for(&contract, 1).unwrap_err();
+            assert_eq!(err_response, InkGroupError::QuorumUnreachable {});
+        }
+
+        #[ink::test]
+        /// The admin can't change its own weight if forbidden by the configuration
+        fn forbid_self_weight_change_works() {
+            let accounts = default_accounts();
+            let config = GroupConfig {
+                forbid_self_weight_change: true,
+                ..Default::default()
+            };
+            let alice_member = Member {
+                addr: accounts.alice,
+                weight: 1,
+            };
+            set_caller(accounts.alice);
+            let mut contract =
+                InkGroupSimple::try_new_with_config(None, vec![alice_member], config).unwrap();
+            let err_response =
+                InkGroupSimple::update_member_weight(&mut contract, accounts.alice, 5).unwrap_err();
+            assert_eq!(err_response, InkGroupError::CannotModifyOwnWeight {});
+            let new_members = vec![
+                Member {
+                    addr: accounts.bob,
+                    weight: 1,
+                },
+                Member {
+                    addr: accounts.alice,
+                    weight: 5,
+                },
+            ];
+            let err_response =
+                InkGroupSimple::update_members(&mut contract, new_members, vec![]).unwrap_err();
+            assert_eq!(err_response, InkGroupError::CannotModifyOwnWeight {});
+            // The weight of the other members can change
+            let bob_member = Member {
+                addr: accounts.bob,
+                weight: 1,
+            };
+            InkGroupSimple::update_members(&mut contract, vec![bob_member], vec![]).unwrap();
+            // A different admin can change it
+            InkGroupSimple::update_admin(&mut contract, accounts.bob).unwrap();
+            set_caller(accounts.bob);
+            InkGroupSimple::update_member_weight(&mut contract, accounts.alice, 5).unwrap();
+            assert_eq!(InkGroupSimple::get_total_weight(&contract), 6);
+            // Off by default
+            set_caller(accounts.alice);
+            let mut contract = build_contract();
+            InkGroupSimple::update_member_weight(&mut contract, accounts.alice, 5).unwrap();
+        }
+
+        #[ink::test]
+        /// Validate a candidate member list against each rule
+        fn validate_member_set_works() {
+            let accounts = default_accounts();
+            let config = GroupConfig {
+                max_total_weight: Some(10),
+                max_member_weight: Some(6),
+                ..Default::default()
+            };
+            let alice_member = Member {
+                addr: accounts.alice,
+                weight: 4,
+            };
+            let bob_member = Member {
+                addr: accounts.bob,
+                weight: 6,
+            };
+            set_caller(accounts.alice);
+            let contract =
+                InkGroupSimple::try_new_with_config(None, vec![alice_member], config).unwrap();
+            assert_eq!(
+                InkGroupSimple::validate_member_set(&contract, vec![alice_member, bob_member]),
+                Ok(())
+            );
+            assert_eq!(
+                InkGroupSimple::validate_member_set(&contract, vec![]),
+                Err(InkGroupError::ZeroMembers {})
+            );
+            assert_eq!(
+                InkGroupSimple::validate_member_set(&contract, vec![alice_member, alice_member]),
+                Err(InkGroupError::DuplicateMember {
+                    member: accounts.alice
+                })
+            );
+            let heavy_member = Member {
+                addr: accounts.charlie,
+                weight: 7,
+            };
+            assert_eq!(
+                InkGroupSimple::validate_member_set(&contract, vec![heavy_member]),
+                Err(InkGroupError::WeightCapExceeded { cap: 6 })
+            );
+            let charlie_member = Member {
+                addr: accounts.charlie,
+                weight: 1,
+            };
+            assert_eq!(
+                InkGroupSimple::validate_member_set(
+                    &contract,
+                    vec![alice_member, bob_member, charlie_member]
+                ),
+                Err(InkGroupError::TotalWeightCapExceeded { cap: 10 })
+            );
+            // Nothing is stored
+            assert_eq!(
+                InkGroupSimple::get_members(&contract).unwrap(),
+                vec![alice_member]
+            );
+            // Without caps only the overflow is left to check
+            let contract = build_contract();
+            let max_members: Vec<Member> = [accounts.alice, accounts.bob]
+                .iter()
+                .map(|&addr| Member {
+                    addr,
+                    weight: u64::MAX,
+                })
+                .collect();
+            assert_eq!(
+                InkGroupSimple::validate_member_set(&contract, max_members),
+                Err(InkGroupError::WeightOverflow {})
+            );
+        }
+
+        #[ink::test]
+        /// Adjust the weights with signed deltas, all or nothing
+        fn adjust_weights_works() {
+            let accounts = default_accounts();
+            let mut contract = build_contract();
+            let charlie_member = Member {
+                addr: accounts.charlie,
+                weight: 5,
+            };
+            InkGroupSimple::update_members(&mut contract, vec![charlie_member], vec![]).unwrap();
+            InkGroupSimple::adjust_weights(
+                &mut contract,
+                vec![
+                    (accounts.alice, 3),
+                    (accounts.charlie, -2),
+                    (accounts.bob, 0),
+                ],
+            )
+            .unwrap();
+            let weights: Vec<u64> = InkGroupSimple::get_members(&contract)
+                .unwrap()
+                .iter()
+                .map(|member| member.weight)
+                .collect();
+            assert_eq!(weights, vec![4, 1, 3]);
+            assert_eq!(InkGroupSimple::get_total_weight(&contract), 8);
+            // One event per changed member
+            let emitted_events = test::recorded_events().collect::<Vec<_>>();
+            let decoded_events = decode_events(emitted_events);
+            assert_eq!(decoded_events.len(), 5);
+            if let Event::MemberWeightChanged(MemberWeightChanged {
+                member,
+                old_weight,
+                new_weight,
+            }) = decoded_events[4]
+            {
+                assert_eq!(member, accounts.charlie);
+                assert_eq!(old_weight, 5);
+                assert_eq!(new_weight, 3);
+            } else {
+                panic!("encountered unexpected event kind: expected a MemberWeightChanged event")
+            }
+            // An underflowing entry rolls back the whole batch
+            let err_response = InkGroupSimple::adjust_weights(
+                &mut contract,
+                vec![(accounts.alice, 1), (accounts.bob, -2)],
+            )
+            .unwrap_err();
+            assert_eq!(err_response, InkGroupError::WeightUnderflow {});
+            let err_response = InkGroupSimple::adjust_weights(
+                &mut contract,
+                vec![(accounts.alice, 1), (accounts.django, 1)],
+            )
+            .unwrap_err();
+            assert_eq!(err_response, InkGroupError::NoMember {});
+            InkGroupSimple::update_member_weight(&mut contract, accounts.alice, u64::MAX - 4)
+                .unwrap();
+            let err_response =
+                InkGroupSimple::adjust_weights(&mut contract, vec![(accounts.alice, 5)])
+                    .unwrap_err();
+            assert_eq!(err_response, InkGroupError::WeightOverflow {});
+            InkGroupSimple::update_member_weight(&mut contract, accounts.alice, 4).unwrap();
+            assert_eq!(InkGroupSimple::get_total_weight(&contract), 8);
+            assert_eq!(
+                InkGroupSimple::get_member(&contract, accounts.alice)
+                    .unwrap()
+                    .weight,
+                4
+            );
+        }
+
+        #[ink::test]
+        /// Get the roster digest
+        fn roster_digest_works() {
+            use ink::env::hash::{Blake2x256, CryptoHash};
+
+            let accounts = default_accounts();
+            let mut contract = build_contract();
+            let digest = InkGroupSimple::roster_digest(&contract).unwrap();
+            // Stable across read-only calls
+            InkGroupSimple::get_members(&contract).unwrap();
+            assert_eq!(InkGroupSimple::roster_digest(&contract).unwrap(), digest);
+            // Reproducible from the getters
+            let roster = (
+                accounts.alice,
+                InkGroupSimple::get_members(&contract).unwrap(),
+                0u64,
+                0u32,
+            );
+            let mut expected = [0u8; 32];
+            Blake2x256::hash(&scale::Encode::encode(&roster), &mut expected);
+            assert_eq!(digest, expected);
+            // Changed by a membership mutation
+            InkGroupSimple::update_member_weight(&mut contract, accounts.bob, 2).unwrap();
+            let mutated_digest = InkGroupSimple::roster_digest(&contract).unwrap();
+            assert_ne!(mutated_digest, digest);
+            // and by an admin update
+            InkGroupSimple::update_admin(&mut contract, accounts.bob).unwrap();
+            assert_ne!(
+                InkGroupSimple::roster_digest(&contract).unwrap(),
+                mutated_digest
+            );
+        }
+
+        #[ink::test]
+        /// Set the role label of a member
+        fn set_member_role_works() {
+            let accounts = default_accounts();
+            let mut contract = build_contract();
+            assert_eq!(
+                InkGroupSimple::get_member_role(&contract, accounts.bob),
+                None
+            );
+            InkGroupSimple::set_member_role(&mut contract, accounts.bob, 2).unwrap();
+            assert_eq!(
+                InkGroupSimple::get_member_role(&contract, accounts.bob),
+                Some(2)
+            );
+            let err_response =
+                InkGroupSimple::set_member_role(&mut contract, accounts.charlie, 2).unwrap_err();
+            assert_eq!(err_response, InkGroupError::NoMember {});
+            // Survives a weight update
+            InkGroupSimple::update_member_weight(&mut contract, accounts.bob, 3).unwrap();
+            assert_eq!(
+                InkGroupSimple::get_member_role(&contract, accounts.bob),
+                Some(2)
+            );
+            // Cleared on removal
+            InkGroupSimple::remove_member(&mut contract, accounts.bob).unwrap();
+            assert_eq!(
+                InkGroupSimple::get_member_role(&contract, accounts.bob),
+                None
+            );
+            let bob_member = Member {
+                addr: accounts.bob,
+                weight: 1,
+            };
+            InkGroupSimple::update_members(&mut contract, vec![bob_member], vec![]).unwrap();
+            assert_eq!(
+                InkGroupSimple::get_member_role(&contract, accounts.bob),
+                None
+            );
+            // Only the admin can
+            set_caller(accounts.bob);
+            let err_response =
+                InkGroupSimple::set_member_role(&mut contract, accounts.bob, 1).unwrap_err();
+            assert_eq!(err_response, InkGroupError::Unauthorized {});
+        }
+
+        #[ink::test]
+        /// Construct the contract from parallel vectors of addresses and weights
+        fn try_new_split_works() {
+            let accounts = default_accounts();
+            set_caller(accounts.alice);
+            let err_response =
+                InkGroupSimple::try_new_split(None, vec![accounts.alice, accounts.bob], vec![1])
+                    .unwrap_err();
+            assert_eq!(
+                err_response,
+                ContractError::InkGroup(InkGroupError::LengthMismatch {})
+            );
+            let contract =
+                InkGroupSimple::try_new_split(None, vec![accounts.alice, accounts.bob], vec![1, 2])
+                    .unwrap();
+            assert_eq!(
+                InkGroupSimple::get_members(&contract).unwrap(),
+                vec![
+                    Member {
+                        addr: accounts.alice,
+                        weight: 1
+                    },
+                    Member {
+                        addr: accounts.bob,
+                        weight: 2
+                    }
+                ]
+            );
+            assert_eq!(InkGroupSimple::get_total_weight(&contract), 3);
+        }
+
+        #[ink::test]
+        /// Sync the members by pages
+        fn sync_checkpoint_works() {
+            let accounts = default_accounts();
+            let mut contract = build_contract();
+            let charlie_member = Member {
+                addr: accounts.charlie,
+                weight: 1,
+            };
+            InkGroupSimple::update_members(&mut contract, vec![charlie_member], vec![]).unwrap();
+            let page = InkGroupSimple::sync_checkpoint(&contract, None, 2).unwrap();
+            assert_eq!(page.members.len(), 2);
+            assert_eq!(page.next_cursor, Some(accounts.bob));
+            assert_eq!(page.epoch, 1);
+            let last_page =
+                InkGroupSimple::sync_checkpoint(&contract, page.next_cursor, 2).unwrap();
+            assert_eq!(last_page.members, vec![charlie_member]);
+            assert_eq!(last_page.next_cursor, None);
+            assert_eq!(last_page.epoch, 1);
+            // A mutation during the sync changes the epoch of the next pages
+            InkGroupSimple::update_member_weight(&mut contract, accounts.charlie, 2).unwrap();
+            let page = InkGroupSimple::sync_checkpoint(&contract, page.next_cursor, 2).unwrap();
+            assert_eq!(page.epoch, 2);
+            // A removed cursor
+            InkGroupSimple::remove_member(&mut contract, accounts.bob).unwrap();
+            let err_response =
+                InkGroupSimple::sync_checkpoint(&contract, Some(accounts.bob), 2).unwrap_err();
+            assert_eq!(err_response, InkGroupError::NoMember {});
+        }
+
+        #[ink::test]
+        /// Set the role label of many members at once
+        fn set_roles_works() {
+            let accounts = default_accounts();
+            let mut contract = build_contract();
+            InkGroupSimple::set_member_role(&mut contract, accounts.alice, 1).unwrap();
+            let emitted_events = test::recorded_events().count();
+            InkGroupSimple::set_roles(&mut contract, vec![(accounts.alice, 1), (accounts.bob, 2)])
+                .unwrap();
+            assert_eq!(
+                InkGroupSimple::get_member_role(&contract, accounts.alice),
+                Some(1)
+            );
+            assert_eq!(
+                InkGroupSimple::get_member_role(&contract, accounts.bob),
+                Some(2)
+            );
+            // Only the changed role emits an update
+            let decoded_events = decode_events(test::recorded_events().collect());
+            assert_eq!(decoded_events.len(), emitted_events + 1);
+            if let Event::MemberUpdate(MemberUpdate { member }) = decoded_events[emitted_events] {
+                assert_eq!(member, accounts.bob);
+            } else {
+                panic!("encountered unexpected event kind: expected a MemberUpdate event")
+            }
+            // A non member rolls back the whole batch
+            let err_response = InkGroupSimple::set_roles(
+                &mut contract,
+                vec![(accounts.alice, 3), (accounts.charlie, 3)],
+            )
+            .unwrap_err();
+            assert_eq!(err_response, InkGroupError::NoMember {});
+            assert_eq!(
+                InkGroupSimple::get_member_role(&contract, accounts.alice),
+                Some(1)
+            );
+            // Only the admin can
+            set_caller(accounts.bob);
+            let err_response =
+                InkGroupSimple::set_roles(&mut contract, vec![(accounts.bob, 1)]).unwrap_err();
+            assert_eq!(err_response, InkGroupError::Unauthorized {});
+        }
+
+        #[ink::test]
+        /// Find the duplicates of a corrupted storage
+        fn find_duplicates_works() {
+            let accounts = default_accounts();
+            let mut contract = build_contract();
+            assert!(InkGroupSimple::find_duplicates(&contract).is_empty());
+            // Corrupt the members bypassing the validation
+            let bob_member = Member {
+                addr: accounts.bob,
+                weight: 1,
+            };
+            let alice_member = Member {
+                addr: accounts.alice,
+                weight: 1,
+            };
+            contract.members.push(bob_member);
+            contract.members.push(alice_member);
+            contract.members.push(bob_member);
+            assert_eq!(
+                InkGroupSimple::find_duplicates(&contract),
+                vec![accounts.alice, accounts.bob]
+            );
+        }
+
+        #[ink::test]
+        /// Repair a corrupted storage
+        fn dedup_members_works() {
+            let accounts = default_accounts();
+            let mut contract = build_contract();
+            assert_eq!(InkGroupSimple::dedup_members(&mut contract), Ok(0));
+            // Corrupt the members bypassing the validation
+            let bob_member = Member {
+                addr: accounts.bob,
+                weight: 2,
+            };
+            contract.members.push(bob_member);
+            contract.members.push(bob_member);
+            contract.total_voting_power.add_weight(1).unwrap();
+            assert!(InkGroupSimple::verify_invariants(&contract).is_err());
+            set_caller(accounts.bob);
+            let err_response = InkGroupSimple::dedup_members(&mut contract).unwrap_err();
+            assert_eq!(err_response, InkGroupError::Unauthorized {});
+            set_caller(accounts.alice);
+            assert_eq!(InkGroupSimple::dedup_members(&mut contract), Ok(2));
+            assert_eq!(
+                InkGroupSimple::get_members(&contract).unwrap(),
+                vec![
+                    Member {
+                        addr: accounts.alice,
+                        weight: 1
+                    },
+                    Member {
+                        addr: accounts.bob,
+                        weight: 5
+                    }
+                ]
+            );
+            assert_eq!(InkGroupSimple::get_total_weight(&contract), 6);
+            assert!(InkGroupSimple::find_duplicates(&contract).is_empty());
+            InkGroupSimple::verify_invariants(&contract).unwrap();
+            let decoded_events = decode_events(test::recorded_events().collect());
+            if let Event::MemberWeightChanged(MemberWeightChanged {
+                member,
+                old_weight,
+                new_weight,
+            }) = decoded_events[decoded_events.len() - 1]
+            {
+                assert_eq!(member, accounts.bob);
+                assert_eq!(old_weight, 1);
+                assert_eq!(new_weight, 5);
+            } else {
+                panic!("encountered unexpected event kind: expected a MemberWeightChanged event")
+            }
+            // A merged weight above the member cap is rejected like on any other path
+            let config = GroupConfig {
+                max_member_weight: Some(3),
+                ..Default::default()
+            };
+            let members = vec![Member {
+                addr: accounts.alice,
+                weight: 1,
+            }];
+            let mut contract = InkGroupSimple::try_new_with_config(None, members, config).unwrap();
+            contract.members.push(bob_member);
+            contract.members.push(bob_member);
+            contract.total_voting_power.add_weight(4).unwrap();
+            let err_response = InkGroupSimple::dedup_members(&mut contract).unwrap_err();
+            assert_eq!(err_response, InkGroupError::WeightCapExceeded { cap: 3 });
+            assert_eq!(
+                InkGroupSimple::find_duplicates(&contract),
+                vec![accounts.bob]
+            );
+        }
+
+        #[ink::test]
+        /// The events of a deduplication keep summing to the unchanged total voting power
+        fn dedup_members_events_sum_to_total() {
+            let accounts = default_accounts();
+            let mut contract = build_contract();
+            // Corrupt the members bypassing the validation, the total includes the duplicates
+            let bob_member = Member {
+                addr: accounts.bob,
+                weight: 2,
+            };
+            contract.members.push(bob_member);
+            contract.members.push(bob_member);
+            contract.total_voting_power.add_weight(4).unwrap();
+            let total = InkGroupSimple::get_total_weight(&contract);
+            // The events of the corruption are missing, the deduplication reports the merge
+            assert_eq!(InkGroupSimple::dedup_members(&mut contract), Ok(2));
+            assert_eq!(InkGroupSimple::get_total_weight(&contract), total);
+            let events_total = decode_events(test::recorded_events().collect())
+                .into_iter()
+                .fold(0i128, |total, event| match event {
+                    Event::MemberAddition(MemberAddition { weight, .. }) => {
+                        total + i128::from(weight)
+                    }
+                    Event::MemberRemoval(MemberRemoval { weight, .. }) => {
+                        total - i128::from(weight)
+                    }
+                    Event::MemberWeightChanged(MemberWeightChanged {
+                        old_weight,
+                        new_weight,
+                        ..
+                    }) => total + i128::from(new_weight) - i128::from(old_weight),
+                    _ => total,
+                });
+            assert_eq!(events_total, i128::from(total));
+            // Bob is still in the group
+            assert_eq!(
+                InkGroupSimple::get_member(&contract, accounts.bob),
+                Ok(Member {
+                    addr: accounts.bob,
+                    weight: 5
+                })
+            );
+        }
+
+        #[ink::test]
+        /// Reject the no-op updates if required by the configuration
+        fn reject_noop_updates_works() {
+            let accounts = default_accounts();
+            let new_members = vec![
+                Member {
+                    addr: accounts.charlie,
+                    weight: 1,
+                },
+                // Bob has already weight 1
+                Member {
+                    addr: accounts.bob,
+                    weight: 1,
+                },
+            ];
+            // Off by default
+            let mut contract = build_contract();
+            InkGroupSimple::update_members(&mut contract, new_members.clone(), vec![]).unwrap();
+            assert_eq!(InkGroupSimple::get_total_weight(&contract), 3);
+
+            let config = GroupConfig {
+                reject_noop_updates: true,
+                ..Default::default()
+            };
+            let members = vec![
+                Member {
+                    addr: accounts.alice,
+                    weight: 1,
+                },
+                Member {
+                    addr: accounts.bob,
+                    weight: 1,
+                },
+            ];
+            set_caller(accounts.alice);
+            let mut contract =
+                InkGroupSimple::try_new_with_config(None, members.clone(), config).unwrap();
+            let err_response =
+                InkGroupSimple::update_members(&mut contract, new_members, vec![]).unwrap_err();
+            assert_eq!(err_response, InkGroupError::NoChange {});
+            assert_eq!(InkGroupSimple::get_members(&contract).unwrap(), members);
+            let err_response =
+                InkGroupSimple::update_member_weight(&mut contract, accounts.bob, 1).unwrap_err();
+            assert_eq!(err_response, InkGroupError::NoChange {});
+            InkGroupSimple::update_member_weight(&mut contract, accounts.bob, 2).unwrap();
+            assert_eq!(InkGroupSimple::get_total_weight(&contract), 3);
+        }
+
+        #[ink::test]
+        /// Get the members changed since an epoch
+        fn members_changed_since_epoch_works() {
+            let accounts = default_accounts();
+            let mut contract = build_contract();
+            assert_eq!(
+                InkGroupSimple::members_changed_since_epoch(&contract, 0).len(),
+                2
+            );
+            assert!(InkGroupSimple::members_changed_since_epoch(&contract, 1).is_empty());
+            // Epoch 1: charlie added
+            let charlie_member = Member {
+                addr: accounts.charlie,
+                weight: 1,
+            };
+            InkGroupSimple::update_members(&mut contract, vec![charlie_member], vec![]).unwrap();
+            // Epoch 2: bob reweighted, alice updated to the same weight
+            let new_members = vec![
+                Member {
+                    addr: accounts.bob,
+                    weight: 3,
+                },
+                Member {
+                    addr: accounts.alice,
+                    weight: 1,
+                },
+            ];
+            InkGroupSimple::update_members(&mut contract, new_members, vec![]).unwrap();
+            assert_eq!(
+                InkGroupSimple::members_changed_since_epoch(&contract, 2),
+                vec![Member {
+                    addr: accounts.bob,
+                    weight: 3
+                }]
+            );
+            assert_eq!(
+                InkGroupSimple::members_changed_since_epoch(&contract, 1),
+                vec![
+                    Member {
+                        addr: accounts.bob,
+                        weight: 3
+                    },
+                    charlie_member
+                ]
+            );
+            // Epoch 3: adjusted weight
+            InkGroupSimple::adjust_weights(&mut contract, vec![(accounts.alice, 1)]).unwrap();
+            assert_eq!(
+                InkGroupSimple::members_changed_since_epoch(&contract, 3),
+                vec![Member {
+                    addr: accounts.alice,
+                    weight: 2
+                }]
+            );
+            // A removed member is not reported
+            InkGroupSimple::remove_member(&mut contract, accounts.bob).unwrap();
+            assert!(InkGroupSimple::members_changed_since_epoch(&contract, 4).is_empty());
+        }
+
+        #[ink::test]
+        /// A locked member can't be removed but can be reweighted
+        fn lock_member_works() {
+            let accounts = default_accounts();
+            let mut contract = build_contract();
+            let err_response =
+                InkGroupSimple::lock_member(&mut contract, accounts.charlie).unwrap_err();
+            assert_eq!(err_response, InkGroupError::NoMember {});
+            InkGroupSimple::lock_member(&mut contract, accounts.bob).unwrap();
+            assert!(InkGroupSimple::is_member_locked(&contract, accounts.bob));
+            let err_response =
+                InkGroupSimple::remove_member(&mut contract, accounts.bob).unwrap_err();
+            assert_eq!(
+                err_response,
+                InkGroupError::MemberLocked {
+                    member: accounts.bob
+                }
+            );
+            let charlie_member = Member {
+                addr: accounts.charlie,
+                weight: 5,
+            };
+            InkGroupSimple::update_members(&mut contract, vec![charlie_member], vec![]).unwrap();
+            let err_response = InkGroupSimple::prune_below_weight(&mut contract, 2).unwrap_err();
+            assert_eq!(
+                err_response,
+                InkGroupError::MemberLocked {
+                    member: accounts.bob
+                }
+            );
+            assert_eq!(InkGroupSimple::get_members(&contract).unwrap().len(), 3);
+            InkGroupSimple::update_member_weight(&mut contract, accounts.bob, 2).unwrap();
+            assert_eq!(InkGroupSimple::get_total_weight(&contract), 8);
+            // Only the admin can
+            set_caller(accounts.bob);
+            let err_response =
+                InkGroupSimple::unlock_member(&mut contract, accounts.bob).unwrap_err();
+            assert_eq!(err_response, InkGroupError::Unauthorized {});
+            set_caller(accounts.alice);
+            InkGroupSimple::unlock_member(&mut contract, accounts.bob).unwrap();
+            assert!(!InkGroupSimple::is_member_locked(&contract, accounts.bob));
+            InkGroupSimple::remove_member(&mut contract, accounts.bob).unwrap();
+        }
+
+        #[ink::test]
+        /// Get the number of coalitions in which each member is pivotal
+        fn pivotal_count_works() {
+            let accounts = default_accounts();
+            let mut contract = build_contract();
+            // Uniform weights: each member is pivotal for the empty coalition only
+            assert_eq!(
+                InkGroupSimple::pivotal_count(&contract, 50),
+                Ok(vec![(accounts.alice, 1), (accounts.bob, 1)])
+            );
+            // Skewed weights: charlie 3, alice and bob 1, a majority needs 3
+            let charlie_member = Member {
+                addr: accounts.charlie,
+                weight: 3,
+            };
+            InkGroupSimple::update_members(&mut contract, vec![charlie_member], vec![]).unwrap();
+            assert_eq!(
+                InkGroupSimple::pivotal_count(&contract, 51),
+                Ok(vec![
+                    (accounts.alice, 0),
+                    (accounts.bob, 0),
+                    (accounts.charlie, 4)
+                ])
+            );
+            // Unanimity: everyone is pivotal for the coalition of all the others
+            assert_eq!(
+                InkGroupSimple::pivotal_count(&contract, 100),
+                Ok(vec![
+                    (accounts.alice, 1),
+                    (accounts.bob, 1),
+                    (accounts.charlie, 1)
+                ])
+            );
+            let err_response = InkGroupSimple::pivotal_count(&contract, 101).unwrap_err();
+            assert_eq!(err_response, InkGroupError::InvalidPercent {});
+            // Too many members
+            let new_members: Vec<Member> = (10..10 + MAX_PIVOTAL_MEMBERS as u8)
+                .map(|byte| Member {
+                    addr: AccountId::from([byte; 32]),
+                    weight: 1,
+                })
+                .collect();
+            InkGroupSimple::update_members(&mut contract, new_members, vec![]).unwrap();
+            let err_response = InkGroupSimple::pivotal_count(&contract, 50).unwrap_err();
+            assert_eq!(
+                err_response,
+                InkGroupError::TooManyMembers {
+                    max: MAX_PIVOTAL_MEMBERS as u32
+                }
+            );
+        }
+
+        #[ink::test]
+        /// Update the admin with the notifications enabled (the off-chain environment can't call
+        /// contracts, so only the admins that are not contracts, and not notified, are covered)
+        fn notify_admin_change_works() {
+            let accounts = default_accounts();
+            let config = GroupConfig {
+                notify_admin_change: true,
+                ..Default::default()
+            };
+            let alice_member = Member {
+                addr: accounts.alice,
+                weight: 1,
+            };
+            set_caller(accounts.alice);
+            let mut contract =
+                InkGroupSimple::try_new_with_config(None, vec![alice_member], config).unwrap();
+            InkGroupSimple::update_admin(&mut contract, accounts.bob).unwrap();
+            assert_eq!(InkGroupSimple::get_admin(&contract), Ok(accounts.bob));
+            set_caller(accounts.bob);
+            InkGroupSimple::update_admin(&mut contract, accounts.bob).unwrap();
+            assert_eq!(InkGroupSimple::get_admin(&contract), Ok(accounts.bob));
+        }
+
+        #[ink::test]
+        /// Get the Gini coefficient of the weights
+        fn weight_gini_bps_works() {
+            let mut contract = build_contract();
+            // Uniform distribution
+            assert_eq!(InkGroupSimple::weight_gini_bps(&contract), Ok(0));
+            // One whale among 10 members
+            let mut new_members: Vec<Member> = (10..18u8)
+                .map(|byte| Member {
+                    addr: AccountId::from([byte; 32]),
+                    weight: 0,
+                })
+                .collect();
+            new_members.push(Member {
+                addr: AccountId::from([1; 32]),
+                weight: 1000,
+            });
+            new_members.push(Member {
+                addr: AccountId::from([2; 32]),
+                weight: 0,
+            });
+            InkGroupSimple::update_members(&mut contract, new_members, vec![]).unwrap();
+            assert_eq!(InkGroupSimple::weight_gini_bps(&contract), Ok(9000));
+            // Zero total voting power
+            InkGroupSimple::update_member_weight(&mut contract, AccountId::from([1; 32]), 0)
+                .unwrap();
+            assert_eq!(InkGroupSimple::weight_gini_bps(&contract), Ok(0));
+        }
+
+        #[ink::test]
+        /// Get the treasury view
+        fn treasury_view_works() {
+            let accounts = default_accounts();
+            let contract = build_contract();
+            test::set_account_balance::<Environment>(test::callee::<Environment>(), 500);
+            assert_eq!(
+                InkGroupSimple::treasury_view(&contract),
+                Ok((accounts.alice, 500, 2))
+            );
+        }
+
+        #[ink::test]
+        /// A member leaves the group
+        fn leave_works() {
+            let accounts = default_accounts();
+            // Off by default
+            let mut contract = build_contract();
+            set_caller(accounts.bob);
+            let err_response = InkGroupSimple::leave(&mut contract).unwrap_err();
+            assert_eq!(err_response, InkGroupError::Unauthorized {});
+
+            let config = GroupConfig {
+                allow_self_leave: true,
+                ..Default::default()
+            };
+            let members = vec![
+                Member {
+                    addr: accounts.alice,
+                    weight: 1,
+                },
+                Member {
+                    addr: accounts.bob,
+                    weight: 2,
+                },
+            ];
+            set_caller(accounts.alice);
+            let mut contract = InkGroupSimple::try_new_with_config(None, members, config).unwrap();
+            set_caller(accounts.bob);
+            InkGroupSimple::leave(&mut contract).unwrap();
+            assert_eq!(
+                InkGroupSimple::get_members(&contract).unwrap(),
+                vec![Member {
+                    addr: accounts.alice,
+                    weight: 1
+                }]
+            );
+            assert_eq!(InkGroupSimple::get_total_weight(&contract), 1);
+            let decoded_events = decode_events(test::recorded_events().collect());
+            if let Event::MemberRemoval(MemberRemoval { member, weight }) =
+                decoded_events[decoded_events.len() - 1]
+            {
+                assert_eq!(member, accounts.bob);
+                assert_eq!(weight, 2);
+            } else {
+                panic!("encountered unexpected event kind: expected a MemberRemoval event")
+            }
+            // Not a member anymore
+            let err_response = InkGroupSimple::leave(&mut contract).unwrap_err();
+            assert_eq!(err_response, InkGroupError::NoMember {});
+            // The last member can't leave
+            set_caller(accounts.alice);
+            let err_response = InkGroupSimple::leave(&mut contract).unwrap_err();
+            assert_eq!(err_response, InkGroupError::ZeroMembers {});
+        }
+
+        #[ink::test]
+        /// Count the members and the voting members
+        fn membership_counts_works() {
+            let accounts = default_accounts();
+            let mut contract = build_contract();
+            assert_eq!(InkGroupSimple::membership_counts(&contract), (2, 2));
+            let observers = vec![
+                Member {
+                    addr: accounts.charlie,
+                    weight: 0,
+                },
+                Member {
+                    addr: accounts.django,
+                    weight: 0,
+                },
+            ];
+            InkGroupSimple::update_members(&mut contract, observers, vec![]).unwrap();
+            assert_eq!(InkGroupSimple::membership_counts(&contract), (4, 2));
+        }
+
+        #[ink::test]
+        /// Get the lowest and highest weights
+        fn min_max_weight_works() {
+            let accounts = default_accounts();
+            let mut contract = build_contract();
+            assert_eq!(InkGroupSimple::min_weight(&contract), Ok(1));
+            assert_eq!(InkGroupSimple::max_weight(&contract), Ok(1));
+            let new_members = vec![
+                Member {
+                    addr: accounts.charlie,
+                    weight: 7,
+                },
+                Member {
+                    addr: accounts.django,
+                    weight: 0,
+                },
+            ];
+            InkGroupSimple::update_members(&mut contract, new_members, vec![]).unwrap();
+            assert_eq!(InkGroupSimple::min_weight(&contract), Ok(0));
+            assert_eq!(InkGroupSimple::max_weight(&contract), Ok(7));
+            // An empty storage
+            contract.members.clear();
+            assert_eq!(
+                InkGroupSimple::min_weight(&contract),
+                Err(InkGroupError::LogicErr {})
+            );
+        }
+
+        #[ink::test]
+        /// The admin can't be a member if required by the configuration
+        fn admin_must_not_be_member_works() {
+            let accounts = default_accounts();
+            let config = GroupConfig {
+                admin_must_not_be_member: true,
+                ..Default::default()
+            };
+            let alice_member = Member {
+                addr: accounts.alice,
+                weight: 1,
+            };
+            let bob_member = Member {
+                addr: accounts.bob,
+                weight: 1,
+            };
+            set_caller(accounts.alice);
+            let err_response =
+                InkGroupSimple::try_new_with_config(None, vec![alice_member, bob_member], config)
+                    .unwrap_err();
+            assert_eq!(
+                err_response,
+                ContractError::InkGroup(InkGroupError::AdminCannotBeMember {})
+            );
+            let mut contract =
+                InkGroupSimple::try_new_with_config(None, vec![bob_member], config).unwrap();
+            let err_response =
+                InkGroupSimple::update_members(&mut contract, vec![alice_member], vec![])
+                    .unwrap_err();
+            assert_eq!(err_response, InkGroupError::AdminCannotBeMember {});
+            let err_response =
+                InkGroupSimple::update_admin(&mut contract, accounts.bob).unwrap_err();
+            assert_eq!(err_response, InkGroupError::AdminCannotBeMember {});
+            // Allowed when off
+            let mut contract = InkGroupSimple::try_new(None, vec![bob_member]).unwrap();
+            InkGroupSimple::update_members(&mut contract, vec![alice_member], vec![]).unwrap();
+        }
+
+        #[ink::test]
+        /// Look up the weights of members and non members
+        fn lookup_weights_works() {
+            let accounts = default_accounts();
+            let mut contract = build_contract();
+            InkGroupSimple::update_member_weight(&mut contract, accounts.bob, 4).unwrap();
+            assert_eq!(
+                InkGroupSimple::lookup_weights(
+                    &contract,
+                    vec![accounts.charlie, accounts.bob, accounts.alice]
+                ),
+                Ok(vec![
+                    (accounts.charlie, None),
+                    (accounts.bob, Some(4)),
+                    (accounts.alice, Some(1))
+                ])
+            );
+            let too_many = vec![accounts.alice; MAX_LOOKUP_ACCOUNTS + 1];
+            let err_response = InkGroupSimple::lookup_weights(&contract, too_many).unwrap_err();
+            assert_eq!(
+                err_response,
+                InkGroupError::InputTooLong {
+                    max: MAX_LOOKUP_ACCOUNTS as u32
+                }
+            );
+        }
+
+        #[ink::test]
+        /// The admin must be a contract if required by the configuration
+        fn require_contract_admin_works() {
+            let accounts = default_accounts();
+            let config = GroupConfig {
+                require_contract_admin: true,
+                ..Default::default()
+            };
+            let member = Member {
+                addr: accounts.bob,
+                weight: 1,
+            };
+            set_caller(accounts.alice);
+            let err_response =
+                InkGroupSimple::try_new_with_config(None, vec![member], config).unwrap_err();
+            assert_eq!(
+                err_response,
+                ContractError::InkGroup(InkGroupError::AdminMustBeContract {})
+            );
+            // Register charlie as a contract in the off-chain environment
+            test::set_contract::<Environment>(accounts.charlie);
+            let mut contract =
+                InkGroupSimple::try_new_with_config(Some(accounts.charlie), vec![member], config)
+                    .unwrap();
+            set_caller(accounts.charlie);
+            let err_response =
+                InkGroupSimple::update_admin(&mut contract, accounts.django).unwrap_err();
+            assert_eq!(err_response, InkGroupError::AdminMustBeContract {});
+            test::set_contract::<Environment>(accounts.django);
+            InkGroupSimple::update_admin(&mut contract, accounts.django).unwrap();
+            assert_eq!(InkGroupSimple::get_admin(&contract), Ok(accounts.django));
+        }
+
+        #[ink::test]
+        /// Members can't be added or removed while the membership is frozen, only reweighted
+        fn freeze_membership_works() {
+            let accounts = default_accounts();
+            let mut contract = build_contract();
+            set_caller(accounts.bob);
+            assert_eq!(
+                InkGroupSimple::freeze_membership(&mut contract),
+                Err(InkGroupError::Unauthorized {})
+            );
+            set_caller(accounts.alice);
+            InkGroupSimple::freeze_membership(&mut contract).unwrap();
+            let frozen = || InkGroupError::InvalidState {
+                reason: InkGroupError::STATE_MEMBERSHIP_FROZEN,
+            };
+            assert!(InkGroupSimple::is_membership_frozen(&contract));
+            let charlie_member = Member {
+                addr: accounts.charlie,
+                weight: 1,
+            };
+            assert_eq!(
+                InkGroupSimple::update_members(&mut contract, vec![charlie_member], vec![]),
+                Err(frozen())
+            );
+            assert_eq!(
+                InkGroupSimple::remove_member(&mut contract, accounts.bob),
+                Err(frozen())
+            );
+            assert_eq!(
+                InkGroupSimple::remove_members(&mut contract, vec![accounts.bob]),
+                Err(frozen())
+            );
+            assert_eq!(
+                InkGroupSimple::remove_member_at(&mut contract, 1),
+                Err(frozen())
+            );
+            // Reweights are still allowed
+            let bob_member = Member {
+                addr: accounts.bob,
+                weight: 3,
+            };
+            InkGroupSimple::update_members(&mut contract, vec![bob_member], vec![]).unwrap();
+            InkGroupSimple::adjust_weights(&mut contract, vec![(accounts.alice, 1)]).unwrap();
+            assert_eq!(InkGroupSimple::get_total_weight(&contract), 5);
+            assert_eq!(
+                InkGroupSimple::prune_below_weight(&mut contract, 3),
+                Err(frozen())
+            );
+            InkGroupSimple::unfreeze_membership(&mut contract).unwrap();
+            assert!(!InkGroupSimple::is_membership_frozen(&contract));
+            InkGroupSimple::update_members(&mut contract, vec![charlie_member], vec![]).unwrap();
+            // Leaving is a removal too
+            let config = GroupConfig {
+                allow_self_leave: true,
+                ..Default::default()
+            };
+            let members = vec![
+                Member {
+                    addr: accounts.alice,
+                    weight: 1,
+                },
+                Member {
+                    addr: accounts.bob,
+                    weight: 1,
+                },
+            ];
+            let mut left_contract =
+                InkGroupSimple::try_new_with_config(None, members, config).unwrap();
+            InkGroupSimple::freeze_membership(&mut left_contract).unwrap();
+            set_caller(accounts.bob);
+            assert_eq!(InkGroupSimple::leave(&mut left_contract), Err(frozen()));
+            let freezes: Vec<bool> = decode_events(test::recorded_events().collect())
+                .into_iter()
+                .filter_map(|event| match event {
+                    Event::MembershipFreezeUpdated(MembershipFreezeUpdated { frozen }) => {
+                        Some(frozen)
+                    }
+                    _ => None,
+                })
+                .collect();
+            assert_eq!(freezes, vec![true, false, true]);
+        }
+
+        #[ink::test]
+        /// The health reflects the current state of the group
+        fn health_works() {
+            let accounts = default_accounts();
+            let mut contract = build_contract();
+            assert_eq!(
+                InkGroupSimple::health(&contract),
+                GroupHealth {
+                    member_count: 2,
+                    total_weight: 2,
+                    admin: Some(accounts.alice),
+                    last_mutated: 0,
+                    epoch: 0,
+                }
+            );
+            test::advance_block::<Environment>();
+            let charlie_member = Member {
+                addr: accounts.charlie,
+                weight: 5,
+            };
+            InkGroupSimple::update_members(&mut contract, vec![charlie_member], vec![accounts.bob])
+                .unwrap();
+            test::advance_block::<Environment>();
+            InkGroupSimple::update_admin(&mut contract, accounts.django).unwrap();
+            assert_eq!(
+                InkGroupSimple::health(&contract),
+                GroupHealth {
+                    member_count: 2,
+                    total_weight: 6,
+                    admin: Some(accounts.django),
+                    last_mutated: 2,
+                    epoch: 1,
+                }
+            );
+        }
+
+        #[ink::test]
+        /// A target distribution is checked against every rule of the group
+        fn distribution_feasible_works() {
+            let accounts = default_accounts();
+            let config = GroupConfig {
+                max_total_weight: Some(10),
+                max_member_weight: Some(6),
+                ..Default::default()
+            };
+            let members = vec![
+                Member {
+                    addr: accounts.alice,
+                    weight: 1,
+                },
+                Member {
+                    addr: accounts.bob,
+                    weight: 1,
+                },
+            ];
+            set_caller(accounts.alice);
+            let contract = InkGroupSimple::try_new_with_config(None, members, config).unwrap();
+            assert_eq!(
+                InkGroupSimple::distribution_feasible(&contract, vec![(accounts.alice, 6)]),
+                Ok(())
+            );
+            assert_eq!(
+                InkGroupSimple::distribution_feasible(
+                    &contract,
+                    vec![(accounts.alice, 6), (accounts.bob, 4)]
+                ),
+                Ok(())
+            );
+            assert_eq!(
+                InkGroupSimple::distribution_feasible(&contract, vec![]),
+                Err(InkGroupError::EmptyUpdate {})
+            );
+            assert_eq!(
+                InkGroupSimple::distribution_feasible(
+                    &contract,
+                    vec![(accounts.bob, 2), (accounts.bob, 3)]
+                ),
+                Err(InkGroupError::DuplicateMember {
+                    member: accounts.bob
+                })
+            );
+            assert_eq!(
+                InkGroupSimple::distribution_feasible(&contract, vec![(accounts.charlie, 1)]),
+                Err(InkGroupError::NoMember {})
+            );
+            assert_eq!(
+                InkGroupSimple::distribution_feasible(&contract, vec![(accounts.alice, 7)]),
+                Err(InkGroupError::WeightCapExceeded { cap: 6 })
+            );
+            assert_eq!(
+                InkGroupSimple::distribution_feasible(
+                    &contract,
+                    vec![(accounts.alice, 6), (accounts.bob, 5)]
+                ),
+                Err(InkGroupError::TotalWeightCapExceeded { cap: 10 })
+            );
+            // Nothing is stored
+            assert_eq!(InkGroupSimple::get_total_weight(&contract), 2);
+        }
+
+        #[ink::test]
+        /// The weight to climb the ranking is the gap to the member at the target rank
+        fn weight_to_rank_works() {
+            let accounts = default_accounts();
+            let members = vec![
+                Member {
+                    addr: accounts.alice,
+                    weight: 10,
+                },
+                Member {
+                    addr: accounts.bob,
+                    weight: 8,
+                },
+                Member {
+                    addr: accounts.charlie,
+                    weight: 6,
+                },
+                Member {
+                    addr: accounts.django,
+                    weight: 3,
+                },
+            ];
+            set_caller(accounts.alice);
+            let mut contract = InkGroupSimple::try_new(None, members).unwrap();
+            // django climbs from rank 4 to rank 2
+            assert_eq!(
+                InkGroupSimple::weight_to_rank(&contract, accounts.django, 2),
+                Ok(5)
+            );
+            assert_eq!(
+                InkGroupSimple::weight_to_rank(&contract, accounts.django, 4),
+                Ok(0)
+            );
+            InkGroupSimple::adjust_weights(&mut contract, vec![(accounts.django, 5)]).unwrap();
+            assert_eq!(
+                InkGroupSimple::weight_to_rank(&contract, accounts.django, 2),
+                Ok(0)
+            );
+            assert_eq!(
+                InkGroupSimple::weight_to_rank(&contract, accounts.django, 1),
+                Ok(2)
+            );
+            // Already above the target rank, or the rank is past the last member
+            assert_eq!(
+                InkGroupSimple::weight_to_rank(&contract, accounts.alice, 3),
+                Ok(0)
+            );
+            assert_eq!(
+                InkGroupSimple::weight_to_rank(&contract, accounts.charlie, 10),
+                Ok(0)
+            );
+            assert_eq!(
+                InkGroupSimple::weight_to_rank(&contract, accounts.eve, 1),
+                Err(InkGroupError::NoMember {})
+            );
+            assert_eq!(
+                InkGroupSimple::weight_to_rank(&contract, accounts.alice, 0),
+                Err(InkGroupError::InvalidRange {})
+            );
+        }
+
+        #[ink::test]
+        /// The configuration is the one entered at construction
+        fn get_config_works() {
+            let accounts = default_accounts();
+            let contract = build_contract();
+            assert_eq!(
+                InkGroupSimple::get_config(&contract),
+                GroupConfig::default()
+            );
+            let config = GroupConfig {
+                max_total_weight: Some(10),
+                max_member_weight: Some(6),
+                reject_noop_updates: true,
+                allow_self_leave: true,
+                ..Default::default()
+            };
+            let member = Member {
+                addr: accounts.bob,
+                weight: 1,
+            };
+            set_caller(accounts.alice);
+            let mut contract =
+                InkGroupSimple::try_new_with_config(None, vec![member], config).unwrap();
+            assert_eq!(InkGroupSimple::get_config(&contract), config);
+            InkGroupSimple::set_total_weight_cap(&mut contract, None).unwrap();
+            assert_eq!(
+                InkGroupSimple::get_config(&contract),
+                GroupConfig {
+                    max_total_weight: None,
+                    ..config
+                }
+            );
+        }
+
+        #[ink::test]
+        /// The weight of a member can't change again before the cooldown elapses
+        fn weight_change_cooldown_works() {
+            let accounts = default_accounts();
+            let config = GroupConfig {
+                weight_change_cooldown_blocks: 2,
+                ..Default::default()
+            };
+            let members = vec![
+                Member {
+                    addr: accounts.alice,
+                    weight: 1,
+                },
+                Member {
+                    addr: accounts.bob,
+                    weight: 1,
+                },
+            ];
+            set_caller(accounts.alice);
+            let mut contract = InkGroupSimple::try_new_with_config(None, members, config).unwrap();
+            // The initial members can change right away
+            InkGroupSimple::update_member_weight(&mut contract, accounts.bob, 2).unwrap();
+            assert_eq!(
+                InkGroupSimple::update_member_weight(&mut contract, accounts.bob, 3),
+                Err(InkGroupError::CooldownActive { available_at: 2 })
+            );
+            assert_eq!(
+                InkGroupSimple::adjust_weights(&mut contract, vec![(accounts.bob, 1)]),
+                Err(InkGroupError::CooldownActive { available_at: 2 })
+            );
+            // Other members and updates to the same weight are not limited
+            InkGroupSimple::update_member_weight(&mut contract, accounts.alice, 2).unwrap();
+            InkGroupSimple::update_member_weight(&mut contract, accounts.bob, 2).unwrap();
+            test::advance_block::<Environment>();
+            assert_eq!(
+                InkGroupSimple::update_member_weight(&mut contract, accounts.bob, 3),
+                Err(InkGroupError::CooldownActive { available_at: 2 })
+            );
+            test::advance_block::<Environment>();
+            InkGroupSimple::update_member_weight(&mut contract, accounts.bob, 3).unwrap();
+            assert_eq!(
+                InkGroupSimple::get_member(&contract, accounts.bob)
+                    .unwrap()
+                    .weight,
+                3
+            );
+        }
+
+        #[ink::test]
+        /// A member is added with its role in one call
+        fn add_member_with_role_works() {
+            let accounts = default_accounts();
+            let mut contract = build_contract();
+            let charlie_member = Member {
+                addr: accounts.charlie,
+                weight: 3,
+            };
+            set_caller(accounts.bob);
+            assert_eq!(
+                InkGroupSimple::add_member_with_role(&mut contract, charlie_member, 2),
+                Err(InkGroupError::Unauthorized {})
+            );
+            set_caller(accounts.alice);
+            InkGroupSimple::add_member_with_role(&mut contract, charlie_member, 2).unwrap();
+            assert_eq!(
+                InkGroupSimple::get_member(&contract, accounts.charlie),
+                Ok(charlie_member)
+            );
+            assert_eq!(
+                InkGroupSimple::get_member_role(&contract, accounts.charlie),
+                Some(2)
+            );
+            let decoded_events = decode_events(test::recorded_events().collect());
+            if let Event::MemberAddition(MemberAddition { member, weight }) =
+                decoded_events[decoded_events.len() - 1]
+            {
+                assert_eq!(member, accounts.charlie);
+                assert_eq!(weight, 3);
+            } else {
+                panic!("encountered unexpected event kind: expected a MemberAddition event")
+            }
+            // An existing member is not updated
+            let bob_member = Member {
+                addr: accounts.bob,
+                weight: 5,
+            };
+            assert_eq!(
+                InkGroupSimple::add_member_with_role(&mut contract, bob_member, 1),
+                Err(InkGroupError::DuplicateMember {
+                    member: accounts.bob
+                })
+            );
+            assert_eq!(
+                InkGroupSimple::get_member_role(&contract, accounts.bob),
+                None
+            );
+        }
+
+        #[ink::test]
+        /// The initial weights are rescaled to sum exactly to the target
+        fn normalize_to_works() {
+            let accounts = default_accounts();
+            let config = GroupConfig {
+                normalize_to: Some(10000),
+                ..Default::default()
+            };
+            let members = vec![
+                Member {
+                    addr: accounts.alice,
+                    weight: 1,
+                },
+                Member {
+                    addr: accounts.bob,
+                    weight: 1,
+                },
+                Member {
+                    addr: accounts.charlie,
+                    weight: 1,
+                },
+            ];
+            set_caller(accounts.alice);
+            let contract = InkGroupSimple::try_new_with_config(None, members, config).unwrap();
+            assert_eq!(InkGroupSimple::get_total_weight(&contract), 10000);
+            // 3333.33 each, the remaining unit goes to the first member
+            let weights: Vec<u64> = InkGroupSimple::get_members(&contract)
+                .unwrap()
+                .iter()
+                .map(|member| member.weight)
+                .collect();
+            assert_eq!(weights, vec![3334, 3333, 3333]);
+            // 1/3 and 2/3 of 10 are 3.33 and 6.67: the unit left goes to the largest remainder
+            let config = GroupConfig {
+                normalize_to: Some(10),
+                ..config
+            };
+            let members = vec![
+                Member {
+                    addr: accounts.alice,
+                    weight: 1,
+                },
+                Member {
+                    addr: accounts.bob,
+                    weight: 2,
+                },
+            ];
+            let contract = InkGroupSimple::try_new_with_config(None, members, config).unwrap();
+            assert_eq!(InkGroupSimple::get_total_weight(&contract), 10);
+            let weights: Vec<u64> = InkGroupSimple::get_members(&contract)
+                .unwrap()
+                .iter()
+                .map(|member| member.weight)
+                .collect();
+            assert_eq!(weights, vec![3, 7]);
+            // Large weights don't overflow
+            let members = vec![
+                Member {
+                    addr: accounts.alice,
+                    weight: u64::MAX / 2,
+                },
+                Member {
+                    addr: accounts.bob,
+                    weight: u64::MAX / 3,
+                },
+            ];
+            let config = GroupConfig {
+                normalize_to: Some(u64::MAX),
+                ..config
+            };
+            let contract = InkGroupSimple::try_new_with_config(None, members, config).unwrap();
+            assert_eq!(InkGroupSimple::get_total_weight(&contract), u64::MAX);
+            let members = vec![Member {
+                addr: accounts.alice,
+                weight: 0,
+            }];
+            let err_response =
+                InkGroupSimple::try_new_with_config(None, members, config).unwrap_err();
+            assert_eq!(
+                err_response,
+                ContractError::InkGroup(InkGroupError::ZeroTotalWeight {})
+            );
+        }
+
+        #[ink::test]
+        /// The weight and the share match the individual getters
+        fn member_weight_and_share_works() {
+            let accounts = default_accounts();
+            let mut contract = build_contract();
+            let charlie_member = Member {
+                addr: accounts.charlie,
+                weight: 1,
+            };
+            InkGroupSimple::update_members(&mut contract, vec![charlie_member], vec![]).unwrap();
+            InkGroupSimple::update_member_weight(&mut contract, accounts.bob, 2).unwrap();
+            let total = InkGroupSimple::get_total_weight(&contract);
+            for (addr, bps) in [
+                (accounts.alice, 2500),
+                (accounts.bob, 5000),
+                (accounts.charlie, 2500),
+            ] {
+                let weight = InkGroupSimple::get_member(&contract, addr).unwrap().weight;
+                assert_eq!(
+                    InkGroupSimple::member_weight_and_share(&contract, addr),
+                    Ok((weight, bps))
+                );
+                assert_eq!(u64::from(bps), weight * 10_000 / total);
+            }
+            assert_eq!(
+                InkGroupSimple::member_weight_and_share(&contract, accounts.django),
+                Err(InkGroupError::NoMember {})
+            );
+            // No share of a zero total
+            InkGroupSimple::update_members(
+                &mut contract,
+                vec![
+                    Member {
+                        addr: accounts.alice,
+                        weight: 0,
+                    },
+                    Member {
+                        addr: accounts.bob,
+                        weight: 0,
+                    },
+                ],
+                vec![accounts.charlie],
+            )
+            .unwrap();
+            assert_eq!(
+                InkGroupSimple::member_weight_and_share(&contract, accounts.bob),
+                Ok((0, 0))
+            );
+        }
+
+        #[ink::test]
+        /// The batch updates are limited to the maximum batch size set by the admin
+        fn set_max_batch_size_works() {
+            let accounts = default_accounts();
+            let mut contract = build_contract();
+            set_caller(accounts.bob);
+            assert_eq!(
+                InkGroupSimple::set_max_batch_size(&mut contract, 1),
+                Err(InkGroupError::Unauthorized {})
+            );
+            set_caller(accounts.alice);
+            InkGroupSimple::set_max_batch_size(&mut contract, 2).unwrap();
+            assert_eq!(
+                InkGroupSimple::get_config(&contract).max_batch_size,
+                Some(2)
+            );
+            let new_members = vec![
+                Member {
+                    addr: accounts.charlie,
+                    weight: 1,
+                },
+                Member {
+                    addr: accounts.django,
+                    weight: 1,
+                },
+            ];
+            assert_eq!(
+                InkGroupSimple::update_members(
+                    &mut contract,
+                    new_members.clone(),
+                    vec![accounts.bob]
+                ),
+                Err(InkGroupError::InputTooLong { max: 2 })
+            );
+            assert_eq!(
+                InkGroupSimple::adjust_weights(
+                    &mut contract,
+                    vec![
+                        (accounts.alice, 1),
+                        (accounts.bob, 1),
+                        (accounts.charlie, 1)
+                    ]
+                ),
+                Err(InkGroupError::InputTooLong { max: 2 })
+            );
+            InkGroupSimple::update_members(&mut contract, new_members, vec![]).unwrap();
+            assert_eq!(
+                InkGroupSimple::set_roles(
+                    &mut contract,
+                    vec![
+                        (accounts.alice, 1),
+                        (accounts.bob, 1),
+                        (accounts.charlie, 1)
+                    ]
+                ),
+                Err(InkGroupError::InputTooLong { max: 2 })
+            );
+            // Clamped to the hard bound
+            InkGroupSimple::set_max_batch_size(&mut contract, u32::MAX).unwrap();
+            assert_eq!(
+                InkGroupSimple::get_config(&contract).max_batch_size,
+                Some(MAX_BATCH_SIZE as u32)
+            );
+            InkGroupSimple::set_max_batch_size(&mut contract, 0).unwrap();
+            assert_eq!(
+                InkGroupSimple::get_config(&contract).max_batch_size,
+                Some(1)
+            );
+        }
+
+        #[ink::test]
+        /// The changes of the total weight cap are recorded, evicting the oldest
+        fn cap_history_works() {
+            let mut contract = build_contract();
+            assert_eq!(InkGroupSimple::cap_history(&contract), vec![]);
+            InkGroupSimple::set_total_weight_cap(&mut contract, Some(10)).unwrap();
+            test::advance_block::<Environment>();
+            InkGroupSimple::set_total_weight_cap(&mut contract, None).unwrap();
+            test::advance_block::<Environment>();
+            InkGroupSimple::set_total_weight_cap(&mut contract, Some(5)).unwrap();
+            assert_eq!(
+                InkGroupSimple::cap_history(&contract),
+                vec![(0, Some(10)), (1, None), (2, Some(5))]
+            );
+            let cap_updates = decode_events(test::recorded_events().collect())
+                .into_iter()
+                .filter(|event| matches!(event, Event::WeightCapUpdated(_)))
+                .count();
+            assert_eq!(cap_updates, 3);
+            for cap in 0..MAX_CAP_HISTORY as u64 {
+                InkGroupSimple::set_total_weight_cap(&mut contract, Some(cap + 100)).unwrap();
+            }
+            let history = InkGroupSimple::cap_history(&contract);
+            assert_eq!(history.len(), MAX_CAP_HISTORY);
+            assert_eq!(history[0], (2, Some(100)));
+            assert_eq!(
+                history[MAX_CAP_HISTORY - 1],
+                (2, Some(MAX_CAP_HISTORY as u64 + 99))
+            );
+        }
+
+        #[ink::test]
+        /// The projected values match an actual removal
+        fn impact_of_removing_works() {
+            let accounts = default_accounts();
+            let mut contract = build_contract();
+            let charlie_member = Member {
+                addr: accounts.charlie,
+                weight: 5,
+            };
+            InkGroupSimple::update_members(&mut contract, vec![charlie_member], vec![]).unwrap();
+            let impact = InkGroupSimple::impact_of_removing(&contract, accounts.charlie).unwrap();
+            assert_eq!(impact, (2, 2));
+            // Nothing is removed
+            assert_eq!(InkGroupSimple::get_total_weight(&contract), 7);
+            InkGroupSimple::remove_member(&mut contract, accounts.charlie).unwrap();
+            assert_eq!(
+                impact,
+                (
+                    InkGroupSimple::get_total_weight(&contract),
+                    InkGroupSimple::membership_counts(&contract).0
+                )
+            );
+            assert_eq!(
+                InkGroupSimple::impact_of_removing(&contract, accounts.charlie),
+                Err(InkGroupError::NoMember {})
+            );
+        }
+
+        #[ink::test]
+        /// Crossing the activation threshold emits one event per transition
+        fn activation_threshold_works() {
+            let accounts = default_accounts();
+            let config = GroupConfig {
+                activation_threshold: Some(5),
+                ..Default::default()
+            };
+            let members = vec![Member {
+                addr: accounts.alice,
+                weight: 2,
+            }];
+            set_caller(accounts.alice);
+            let mut contract = InkGroupSimple::try_new_with_config(None, members, config).unwrap();
+            let bob_member = Member {
+                addr: accounts.bob,
+                weight: 3,
+            };
+            // Up to the threshold, then above it
+            InkGroupSimple::update_members(&mut contract, vec![bob_member], vec![]).unwrap();
+            InkGroupSimple::adjust_weights(&mut contract, vec![(accounts.bob, 1)]).unwrap();
+            // Below it, still below, then back up
+            InkGroupSimple::remove_member(&mut contract, accounts.bob).unwrap();
+            InkGroupSimple::update_member_weight(&mut contract, accounts.alice, 4).unwrap();
+            InkGroupSimple::update_member_weight(&mut contract, accounts.alice, 5).unwrap();
+            let transitions: Vec<(bool, u64)> = decode_events(test::recorded_events().collect())
+                .into_iter()
+                .filter_map(|event| match event {
+                    Event::ThresholdReached(ThresholdReached { threshold }) => {
+                        Some((true, threshold))
+                    }
+                    Event::ThresholdLost(ThresholdLost { threshold }) => Some((false, threshold)),
+                    _ => None,
+                })
+                .collect();
+            assert_eq!(transitions, vec![(true, 5), (false, 5), (true, 5)]);
+            // A threshold above the cap could never be reached
+            let config = GroupConfig {
+                activation_threshold: Some(5),
+                max_total_weight: Some(4),
+                ..Default::default()
+            };
+            let members = vec![Member {
+                addr: accounts.alice,
+                weight: 2,
+            }];
+            let err_response =
+                InkGroupSimple::try_new_with_config(None, members, config).unwrap_err();
+            assert_eq!(
+                err_response,
+                ContractError::InkGroup(InkGroupError::InvalidConfig {})
+            );
+        }
+
+        #[ink::test]
+        /// The CSV export parses back to the same members
+        fn export_csv_works() {
+            let accounts = default_accounts();
+            let mut contract = build_contract();
+            let charlie_member = Member {
+                addr: accounts.charlie,
+                weight: u64::MAX - 2,
+            };
+            InkGroupSimple::update_members(&mut contract, vec![charlie_member], vec![]).unwrap();
+            let csv = String::from_utf8(InkGroupSimple::export_csv(&contract)).unwrap();
+            assert!(csv.starts_with(&format!("0x{},1\n", "01".repeat(32))));
+            let parsed: Vec<Member> = csv
+                .lines()
+                .map(|row| {
+                    let (addr, weight) = row.split_once(',').unwrap();
+                    let bytes: Vec<u8> = (2..addr.len())
+                        .step_by(2)
+                        .map(|i| u8::from_str_radix(&addr[i..i + 2], 16).unwrap())
+                        .collect();
+                    Member {
+                        addr: AccountId::try_from(bytes.as_slice()).unwrap(),
+                        weight: weight.parse().unwrap(),
+                    }
+                })
+                .collect();
+            let mut members = InkGroupSimple::get_members(&contract).unwrap();
+            members.sort_unstable_by_key(|member| member.addr);
+            assert_eq!(parsed, members);
+        }
+
+        #[ink::test]
+        /// Only the admin can upgrade the contract
+        fn upgrade_works() {
+            // The off-chain environment doesn't support `set_code_hash`, only the authorization
+            // guard can be tested here (see `e2e_upgrade_unknown_code_hash_fails`)
+            let accounts = default_accounts();
+            let mut contract = build_contract();
+            set_caller(accounts.bob);
+            assert_eq!(
+                InkGroupSimple::upgrade(&mut contract, Hash::from([1; 32])),
+                Err(InkGroupError::Unauthorized {})
+            );
+        }
+
+        #[ink::test]
+        /// The median of odd and even member counts
+        fn median_weight_works() {
+            let accounts = default_accounts();
+            let members = vec![
+                Member {
+                    addr: accounts.alice,
+                    weight: 100,
+                },
+                Member {
+                    addr: accounts.bob,
+                    weight: 2,
+                },
+                Member {
+                    addr: accounts.charlie,
+                    weight: 3,
+                },
+            ];
+            set_caller(accounts.alice);
+            let mut contract = InkGroupSimple::try_new(None, members).unwrap();
+            assert_eq!(InkGroupSimple::median_weight(&contract), Ok(3));
+            // Lower middle of 1, 2, 3, 100
+            let django_member = Member {
+                addr: accounts.django,
+                weight: 1,
+            };
+            InkGroupSimple::update_members(&mut contract, vec![django_member], vec![]).unwrap();
+            assert_eq!(InkGroupSimple::median_weight(&contract), Ok(2));
+            contract.members.clear();
+            assert_eq!(
+                InkGroupSimple::median_weight(&contract),
+                Err(InkGroupError::LogicErr {})
+            );
+        }
+
+        #[ink::test]
+        /// The leaderboard is consistent with the individual getters
+        fn leaderboard_works() {
+            let accounts = default_accounts();
+            let members = vec![
+                Member {
+                    addr: accounts.alice,
+                    weight: 2,
+                },
+                Member {
+                    addr: accounts.bob,
+                    weight: 5,
+                },
+                Member {
+                    addr: accounts.charlie,
+                    weight: 2,
+                },
+                Member {
+                    addr: accounts.django,
+                    weight: 1,
+                },
+            ];
+            set_caller(accounts.alice);
+            let contract = InkGroupSimple::try_new(None, members).unwrap();
+            let leaderboard = InkGroupSimple::leaderboard(&contract, 3);
+            assert_eq!(
+                leaderboard,
+                vec![
+                    (1, accounts.bob, 5, 5000),
+                    (2, accounts.alice, 2, 2000),
+                    (2, accounts.charlie, 2, 2000)
+                ]
+            );
+            for (rank, addr, weight, bps) in leaderboard {
+                assert_eq!(
+                    InkGroupSimple::member_weight_and_share(&contract, addr),
+                    Ok((weight, bps))
+                );
+                assert_eq!(InkGroupSimple::weight_to_rank(&contract, addr, rank), Ok(0));
+            }
+            // Capped to the member count
+            let leaderboard = InkGroupSimple::leaderboard(&contract, 10);
+            assert_eq!(leaderboard.len(), 4);
+            assert_eq!(leaderboard[3], (4, accounts.django, 1, 1000));
+            assert_eq!(InkGroupSimple::leaderboard(&contract, 0), vec![]);
+        }
+
+        #[ink::test]
+        /// The tied members are listed by address whatever the storage order, like the committee
+        fn leaderboard_ties_by_address() {
+            let accounts = default_accounts();
+            let members = vec![
+                Member {
+                    addr: accounts.django,
+                    weight: 3,
+                },
+                Member {
+                    addr: accounts.charlie,
+                    weight: 3,
+                },
+                Member {
+                    addr: accounts.bob,
+                    weight: 3,
+                },
+                Member {
+                    addr: accounts.alice,
+                    weight: 1,
+                },
+            ];
+            set_caller(accounts.alice);
+            let contract = InkGroupSimple::try_new(None, members).unwrap();
+            let leaderboard = InkGroupSimple::leaderboard(&contract, 2);
+            assert_eq!(
+                leaderboard,
+                vec![(1, accounts.bob, 3, 3000), (1, accounts.charlie, 3, 3000)]
+            );
+            let leaders: Vec<AccountId> = leaderboard.iter().map(|(_, addr, _, _)| *addr).collect();
+            assert_eq!(leaders, InkGroupSimple::committee(&contract, 2));
+        }
+
+        #[ink::test]
+        /// Only the weight of the members with the role is summed
+        fn role_weight_works() {
+            let accounts = default_accounts();
+            let members = vec![
+                Member {
+                    addr: accounts.alice,
+                    weight: 2,
+                },
+                Member {
+                    addr: accounts.bob,
+                    weight: 3,
+                },
+                Member {
+                    addr: accounts.charlie,
+                    weight: 4,
+                },
+            ];
+            set_caller(accounts.alice);
+            let mut contract = InkGroupSimple::try_new(None, members).unwrap();
+            assert_eq!(InkGroupSimple::role_weight(&contract, 1), 0);
+            InkGroupSimple::set_roles(
+                &mut contract,
+                vec![
+                    (accounts.alice, 1),
+                    (accounts.bob, 2),
+                    (accounts.charlie, 1),
+                ],
+            )
+            .unwrap();
+            assert_eq!(InkGroupSimple::role_weight(&contract, 1), 6);
+            assert_eq!(InkGroupSimple::role_weight(&contract, 2), 3);
+            assert_eq!(InkGroupSimple::role_weight(&contract, 3), 0);
+            // A removed member no longer counts
+            InkGroupSimple::update_members(&mut contract, vec![], vec![accounts.charlie]).unwrap();
+            assert_eq!(InkGroupSimple::role_weight(&contract, 1), 2);
+        }
+
+        #[ink::test]
+        /// The share of a member is relative to the weight of its role
+        fn role_share_bps_works() {
+            let accounts = default_accounts();
+            let members = vec![
+                Member {
+                    addr: accounts.alice,
+                    weight: 4,
+                },
+                Member {
+                    addr: accounts.bob,
+                    weight: 3,
+                },
+                Member {
+                    addr: accounts.charlie,
+                    weight: 3,
+                },
+                Member {
+                    addr: accounts.django,
+                    weight: 90,
+                },
+            ];
+            set_caller(accounts.alice);
+            let mut contract = InkGroupSimple::try_new(None, members).unwrap();
+            InkGroupSimple::set_roles(
+                &mut contract,
+                vec![
+                    (accounts.alice, 1),
+                    (accounts.bob, 1),
+                    (accounts.charlie, 1),
+                    (accounts.django, 2),
+                ],
+            )
+            .unwrap();
+            // 4 of the 10 of the directors, not of the total 100
+            assert_eq!(
+                InkGroupSimple::role_share_bps(&contract, accounts.alice, 1),
+                Ok(4_000)
+            );
+            assert_eq!(
+                InkGroupSimple::role_share_bps(&contract, accounts.bob, 1),
+                Ok(3_000)
+            );
+            assert_eq!(
+                InkGroupSimple::role_share_bps(&contract, accounts.django, 2),
+                Ok(10_000)
+            );
+            // Another role or a non-member
+            assert_eq!(
+                InkGroupSimple::role_share_bps(&contract, accounts.alice, 2),
+                Err(InkGroupError::NoMember {})
+            );
+            assert_eq!(
+                InkGroupSimple::role_share_bps(&contract, accounts.eve, 1),
+                Err(InkGroupError::NoMember {})
+            );
+        }
+
+        #[ink::test]
+        /// Two batches with the same members in a different order give the same state and events
+        fn update_members_order_is_deterministic() {
+            let accounts = default_accounts();
+            let member = |addr, weight| Member { addr, weight };
+            let initial_members = vec![
+                member(accounts.alice, 1),
+                member(accounts.bob, 2),
+                member(accounts.django, 3),
+            ];
+            set_caller(accounts.alice);
+            let mut first = InkGroupSimple::try_new(None, initial_members.clone()).unwrap();
+            let mut second = InkGroupSimple::try_new(None, initial_members).unwrap();
+            let mut batch_events = Vec::new();
+            for (contract, new_members, remove_members) in [
+                (
+                    &mut first,
+                    vec![
+                        member(accounts.eve, 3),
+                        member(accounts.charlie, 2),
+                        member(accounts.bob, 4),
+                    ],
+                    vec![accounts.django, accounts.alice],
+                ),
+                (
+                    &mut second,
+                    vec![
+                        member(accounts.bob, 4),
+                        member(accounts.charlie, 2),
+                        member(accounts.eve, 3),
+                    ],
+                    vec![accounts.alice, accounts.django],
+                ),
+            ] {
+                let recorded = test::recorded_events().count();
+                InkGroupSimple::update_members(contract, new_members, remove_members).unwrap();
+                let events: Vec<Vec<u8>> = test::recorded_events()
+                    .skip(recorded)
+                    .map(|event| event.data)
+                    .collect();
+                batch_events.push(events);
+            }
+            assert_eq!(batch_events[0].len(), 5);
+            assert_eq!(batch_events[0], batch_events[1]);
+            assert_eq!(
+                InkGroupSimple::get_members(&first),
+                InkGroupSimple::get_members(&second)
+            );
+            assert_eq!(
+                InkGroupSimple::get_total_weight(&first),
+                InkGroupSimple::get_total_weight(&second)
+            );
+        }
+
+        #[ink::test]
+        /// The roles are returned by position, `None` without a role or for non-members
+        fn get_roles_works() {
+            let accounts = default_accounts();
+            let mut contract = build_contract();
+            InkGroupSimple::set_member_role(&mut contract, accounts.alice, 1).unwrap();
+            assert_eq!(
+                InkGroupSimple::get_roles(
+                    &contract,
+                    vec![accounts.charlie, accounts.bob, accounts.alice]
+                ),
+                Ok(vec![None, None, Some(1)])
+            );
+            assert_eq!(InkGroupSimple::get_roles(&contract, vec![]), Ok(vec![]));
+            assert_eq!(
+                InkGroupSimple::get_roles(&contract, vec![accounts.alice; MAX_LOOKUP_ACCOUNTS + 1]),
+                Err(InkGroupError::InputTooLong {
+                    max: MAX_LOOKUP_ACCOUNTS as u32
+                })
+            );
+        }
+
+        #[ink::test]
+        /// The admin can't change again before the cooldown elapses
+        fn admin_change_cooldown_works() {
+            let accounts = default_accounts();
+            let config = GroupConfig {
+                admin_change_cooldown_blocks: 2,
+                ..Default::default()
+            };
+            let member = Member {
+                addr: accounts.django,
+                weight: 1,
+            };
+            set_caller(accounts.alice);
+            let mut contract =
+                InkGroupSimple::try_new_with_config(None, vec![member], config).unwrap();
+            // The admin set at construction can be changed right away
+            InkGroupSimple::update_admin(&mut contract, accounts.bob).unwrap();
+            set_caller(accounts.bob);
+            assert_eq!(
+                InkGroupSimple::update_admin(&mut contract, accounts.charlie),
+                Err(InkGroupError::AdminCooldownActive { available_at: 2 })
+            );
+            test::advance_block::<Environment>();
+            assert_eq!(
+                InkGroupSimple::update_admin(&mut contract, accounts.charlie),
+                Err(InkGroupError::AdminCooldownActive { available_at: 2 })
+            );
+            test::advance_block::<Environment>();
+            InkGroupSimple::update_admin(&mut contract, accounts.charlie).unwrap();
+            assert_eq!(InkGroupSimple::get_admin(&contract), Ok(accounts.charlie));
+        }
+
+        #[ink::test]
+        /// A construction with a zero total voting power is rejected only with the flag
+        fn require_positive_total_works() {
+            let accounts = default_accounts();
+            let members = vec![
+                Member {
+                    addr: accounts.alice,
+                    weight: 0,
+                },
+                Member {
+                    addr: accounts.bob,
+                    weight: 0,
+                },
+            ];
+            let config = GroupConfig {
+                require_positive_total: true,
+                ..Default::default()
+            };
+            set_caller(accounts.alice);
+            let err_response =
+                InkGroupSimple::try_new_with_config(None, members.clone(), config).unwrap_err();
+            assert_eq!(
+                err_response,
+                ContractError::InkGroup(InkGroupError::ZeroTotalWeight {})
+            );
+            // Permissive by default
+            let contract = InkGroupSimple::try_new(None, members).unwrap();
+            assert_eq!(InkGroupSimple::get_total_weight(&contract), 0);
+            let member = Member {
+                addr: accounts.alice,
+                weight: 1,
+            };
+            assert!(InkGroupSimple::try_new_with_config(None, vec![member], config).is_ok());
+        }
+
+        #[ink::test]
+        /// The weight of the member is set and it becomes the admin in one call
+        fn promote_to_controller_works() {
+            let accounts = default_accounts();
+            let config = GroupConfig {
+                max_member_weight: Some(10),
+                ..Default::default()
+            };
+            let members = vec![
+                Member {
+                    addr: accounts.alice,
+                    weight: 1,
+                },
+                Member {
+                    addr: accounts.bob,
+                    weight: 1,
+                },
+            ];
+            set_caller(accounts.alice);
+            let mut contract = InkGroupSimple::try_new_with_config(None, members, config).unwrap();
+            assert_eq!(
+                InkGroupSimple::promote_to_controller(&mut contract, accounts.charlie, 5),
+                Err(InkGroupError::NoMember {})
+            );
+            // Nothing is applied if the weight update fails
+            assert_eq!(
+                InkGroupSimple::promote_to_controller(&mut contract, accounts.bob, 11),
+                Err(InkGroupError::WeightCapExceeded { cap: 10 })
+            );
+            assert_eq!(InkGroupSimple::get_admin(&contract), Ok(accounts.alice));
+            let recorded = test::recorded_events().count();
+            InkGroupSimple::promote_to_controller(&mut contract, accounts.bob, 5).unwrap();
+            assert_eq!(InkGroupSimple::get_admin(&contract), Ok(accounts.bob));
+            assert_eq!(
+                InkGroupSimple::get_member(&contract, accounts.bob)
+                    .unwrap()
+                    .weight,
+                5
+            );
+            assert_eq!(InkGroupSimple::get_total_weight(&contract), 6);
+            let decoded_events = decode_events(test::recorded_events().skip(recorded).collect());
+            assert_eq!(decoded_events.len(), 3);
+            assert!(matches!(
+                decoded_events[0],
+                Event::MemberUpdate(MemberUpdate { member }) if member == accounts.bob
+            ));
+            assert!(matches!(
+                decoded_events[1],
+                Event::MemberWeightChanged(MemberWeightChanged {
+                    member,
+                    old_weight: 1,
+                    new_weight: 5,
+                }) if member == accounts.bob
+            ));
+            assert!(matches!(
+                decoded_events[2],
+                Event::AdminUpdate(AdminUpdate { old_admin, new_admin })
+                    if old_admin == accounts.alice && new_admin == accounts.bob
+            ));
+            // The old admin lost its rights
+            assert_eq!(
+                InkGroupSimple::promote_to_controller(&mut contract, accounts.alice, 5),
+                Err(InkGroupError::Unauthorized {})
+            );
+        }
+
+        #[ink::test]
+        /// The remaining capacity follows the number of members up to the cap
+        fn remaining_capacity_works() {
+            let accounts = default_accounts();
+            let contract = build_contract();
+            assert_eq!(InkGroupSimple::remaining_capacity(&contract), None);
+            let config = GroupConfig {
+                max_members: Some(3),
+                ..Default::default()
+            };
+            let member = |addr| Member { addr, weight: 1 };
+            let mut contract =
+                InkGroupSimple::try_new_with_config(None, vec![member(accounts.alice)], config)
+                    .unwrap();
+            assert_eq!(InkGroupSimple::remaining_capacity(&contract), Some(2));
+            InkGroupSimple::update_members(&mut contract, vec![member(accounts.bob)], vec![])
+                .unwrap();
+            assert_eq!(InkGroupSimple::remaining_capacity(&contract), Some(1));
+            InkGroupSimple::update_members(&mut contract, vec![member(accounts.charlie)], vec![])
+                .unwrap();
+            assert_eq!(InkGroupSimple::remaining_capacity(&contract), Some(0));
+            assert_eq!(
+                InkGroupSimple::update_members(
+                    &mut contract,
+                    vec![member(accounts.django)],
+                    vec![]
+                ),
+                Err(InkGroupError::TooManyMembers { max: 3 })
+            );
+            // A batch that removes as many members as it adds still fits
+            InkGroupSimple::update_members(
+                &mut contract,
+                vec![member(accounts.django)],
+                vec![accounts.charlie],
+            )
+            .unwrap();
+            assert_eq!(InkGroupSimple::remaining_capacity(&contract), Some(0));
+            let members = vec![
+                member(accounts.alice),
+                member(accounts.bob),
+                member(accounts.charlie),
+                member(accounts.django),
+            ];
+            assert_eq!(
+                InkGroupSimple::try_new_with_config(None, members, config).unwrap_err(),
+                ContractError::InkGroup(InkGroupError::TooManyMembers { max: 3 })
+            );
+        }
+
+        #[ink::test]
+        /// Only two members are both members
+        fn both_members_works() {
+            let accounts = default_accounts();
+            let contract = build_contract();
+            assert!(InkGroupSimple::both_members(
+                &contract,
+                accounts.alice,
+                accounts.bob
+            ));
+            assert!(!InkGroupSimple::both_members(
+                &contract,
+                accounts.alice,
+                accounts.charlie
+            ));
+            assert!(!InkGroupSimple::both_members(
+                &contract,
+                accounts.charlie,
+                accounts.bob
+            ));
+            assert!(!InkGroupSimple::both_members(
+                &contract,
+                accounts.charlie,
+                accounts.django
+            ));
+        }
+
+        #[ink::test]
+        /// The weight of the admin is its weight as a member, 0 if not a member
+        fn admin_weight_works() {
+            let accounts = default_accounts();
+            let mut contract = build_contract();
+            InkGroupSimple::update_member_weight(&mut contract, accounts.alice, 3).unwrap();
+            assert_eq!(InkGroupSimple::admin_weight(&contract), 3);
+            InkGroupSimple::update_admin(&mut contract, accounts.charlie).unwrap();
+            assert_eq!(InkGroupSimple::admin_weight(&contract), 0);
+        }
+
+        #[ink::test]
+        /// The membership changes are recorded and the oldest are evicted first
+        fn recent_changes_works() {
+            let accounts = default_accounts();
+            let contract = build_contract();
+            assert_eq!(InkGroupSimple::recent_changes(&contract, 10), vec![]);
+            let config = GroupConfig {
+                mirror_events: true,
+                ..Default::default()
+            };
+            let alice_member = Member {
+                addr: accounts.alice,
+                weight: 1,
+            };
+            set_caller(accounts.alice);
+            let mut contract =
+                InkGroupSimple::try_new_with_config(None, vec![alice_member], config).unwrap();
+            let record = |kind, member, weight_delta, block| ChangeRecord {
+                kind,
+                member,
+                weight_delta,
+                block,
+            };
+            InkGroupSimple::update_members(
+                &mut contract,
+                vec![Member {
+                    addr: accounts.bob,
+                    weight: 2,
+                }],
+                vec![],
+            )
+            .unwrap();
+            test::advance_block::<Environment>();
+            InkGroupSimple::update_member_weight(&mut contract, accounts.bob, 5).unwrap();
+            InkGroupSimple::remove_member(&mut contract, accounts.bob).unwrap();
+            assert_eq!(
+                InkGroupSimple::recent_changes(&contract, 10),
+                vec![
+                    record(ChangeKind::Added, accounts.alice, 1, 0),
+                    record(ChangeKind::Added, accounts.bob, 2, 0),
+                    record(ChangeKind::Updated, accounts.bob, 3, 1),
+                    record(ChangeKind::Removed, accounts.bob, -5, 1)
+                ]
+            );
+            assert_eq!(
+                InkGroupSimple::recent_changes(&contract, 1),
+                vec![record(ChangeKind::Removed, accounts.bob, -5, 1)]
+            );
+            for weight in 2..2 + MAX_CHANGE_RECORDS as u64 {
+                InkGroupSimple::update_member_weight(&mut contract, accounts.alice, weight)
+                    .unwrap();
+            }
+            let records = InkGroupSimple::recent_changes(&contract, u32::MAX);
+            assert_eq!(records.len(), MAX_CHANGE_RECORDS);
+            assert!(records
+                .iter()
+                .all(|record| record.kind == ChangeKind::Updated && record.weight_delta == 1));
         }
 
         #[ink::test]
-        /// Get the members of the group
-        fn get_members_works() {
+        /// A spin off can't move non-members or empty either group
+        fn spin_off_guards_work() {
+            // The off-chain environment doesn't support the instantiation of contracts, the
+            // spin off itself is tested end-to-end
+            let accounts = default_accounts();
+            let mut contract = build_contract();
+            let code_hash = Hash::from([1; 32]);
+            assert_eq!(
+                InkGroupSimple::spin_off(&mut contract, vec![accounts.charlie], code_hash),
+                Err(InkGroupError::NoMember {})
+            );
+            assert_eq!(
+                InkGroupSimple::spin_off(&mut contract, vec![], code_hash),
+                Err(InkGroupError::ZeroMembers {})
+            );
+            assert_eq!(
+                InkGroupSimple::spin_off(
+                    &mut contract,
+                    vec![accounts.alice, accounts.bob],
+                    code_hash
+                ),
+                Err(InkGroupError::ZeroMembers {})
+            );
+            assert_eq!(
+                InkGroupSimple::spin_off(
+                    &mut contract,
+                    vec![accounts.bob, accounts.bob],
+                    code_hash
+                ),
+                Err(InkGroupError::DuplicateMember {
+                    member: accounts.bob
+                })
+            );
+            assert_eq!(InkGroupSimple::get_members(&contract).unwrap().len(), 2);
+            set_caller(accounts.bob);
+            assert_eq!(
+                InkGroupSimple::spin_off(&mut contract, vec![accounts.bob], code_hash),
+                Err(InkGroupError::Unauthorized {})
+            );
+        }
+
+        #[ink::test]
+        /// The members are bucketed in their power of ten tier
+        fn tier_distribution_works() {
+            let accounts = default_accounts();
+            let weights = [
+                (accounts.alice, 0),
+                (accounts.bob, 1),
+                (accounts.charlie, 9),
+                (accounts.django, 150),
+                (accounts.eve, 999),
+                (accounts.frank, u64::MAX - 1159),
+            ];
+            let members = weights
+                .iter()
+                .map(|(addr, weight)| Member {
+                    addr: *addr,
+                    weight: *weight,
+                })
+                .collect();
+            set_caller(accounts.alice);
+            let contract = InkGroupSimple::try_new(None, members).unwrap();
+            assert_eq!(
+                InkGroupSimple::tier_distribution(&contract),
+                vec![
+                    (0, 1, 0),
+                    (1, 2, 10),
+                    (100, 2, 1149),
+                    (10_000_000_000_000_000_000, 1, u64::MAX - 1159)
+                ]
+            );
+        }
+
+        #[ink::test]
+        /// Only the members differing from the desired ones are touched
+        fn sync_to_works() {
+            let accounts = default_accounts();
+            let members = vec![
+                Member {
+                    addr: accounts.alice,
+                    weight: 1,
+                },
+                Member {
+                    addr: accounts.bob,
+                    weight: 2,
+                },
+                Member {
+                    addr: accounts.charlie,
+                    weight: 3,
+                },
+            ];
+            set_caller(accounts.alice);
+            let mut contract = InkGroupSimple::try_new(None, members).unwrap();
+            InkGroupSimple::set_member_role(&mut contract, accounts.alice, 7).unwrap();
+            let desired = vec![
+                Member {
+                    addr: accounts.alice,
+                    weight: 1,
+                },
+                Member {
+                    addr: accounts.bob,
+                    weight: 5,
+                },
+                Member {
+                    addr: accounts.django,
+                    weight: 4,
+                },
+            ];
+            let recorded = test::recorded_events().count();
+            assert_eq!(
+                InkGroupSimple::sync_to(&mut contract, desired.clone()),
+                Ok(UpdateReport {
+                    added: 1,
+                    updated: 1,
+                    removed: 1
+                })
+            );
+            let decoded_events = decode_events(test::recorded_events().skip(recorded).collect());
+            assert_eq!(decoded_events.len(), 3);
+            assert!(decoded_events.iter().all(|event| match event {
+                Event::MemberUpdate(MemberUpdate { member }) => *member == accounts.bob,
+                Event::MemberAddition(MemberAddition { member, .. }) => *member == accounts.django,
+                Event::MemberRemoval(MemberRemoval { member, .. }) => *member == accounts.charlie,
+                _ => false,
+            }));
+            let mut members = InkGroupSimple::get_members(&contract).unwrap();
+            members.sort_unstable_by_key(|member| member.addr);
+            assert_eq!(members, desired);
+            assert_eq!(InkGroupSimple::get_total_weight(&contract), 10);
+            // The untouched member keeps its metadata
+            assert_eq!(
+                InkGroupSimple::get_member_role(&contract, accounts.alice),
+                Some(7)
+            );
+            // Nothing to do once in sync
+            assert_eq!(
+                InkGroupSimple::sync_to(&mut contract, desired),
+                Ok(UpdateReport::default())
+            );
+            assert_eq!(
+                InkGroupSimple::sync_to(&mut contract, vec![]),
+                Err(InkGroupError::ZeroMembers {})
+            );
+        }
+
+        #[ink::test]
+        /// The admin actions are recorded in order with their actor
+        fn admin_actions_works() {
+            let accounts = default_accounts();
+            let mut contract = build_contract();
+            assert_eq!(InkGroupSimple::admin_actions(&contract, 10), vec![]);
+            let action = |actor, kind, block| ActionRecord { actor, kind, block };
+            InkGroupSimple::update_member_weight(&mut contract, accounts.bob, 2).unwrap();
+            InkGroupSimple::set_member_role(&mut contract, accounts.bob, 1).unwrap();
+            test::advance_block::<Environment>();
+            InkGroupSimple::lock_member(&mut contract, accounts.bob).unwrap();
+            InkGroupSimple::set_total_weight_cap(&mut contract, Some(10)).unwrap();
+            InkGroupSimple::update_admin(&mut contract, accounts.charlie).unwrap();
+            set_caller(accounts.charlie);
+            InkGroupSimple::freeze_membership(&mut contract).unwrap();
+            assert_eq!(
+                InkGroupSimple::admin_actions(&contract, 10),
+                vec![
+                    action(accounts.alice, ActionKind::MembersUpdated, 0),
+                    action(accounts.alice, ActionKind::RolesUpdated, 0),
+                    action(accounts.alice, ActionKind::LocksUpdated, 1),
+                    action(accounts.alice, ActionKind::ConfigUpdated, 1),
+                    action(accounts.alice, ActionKind::AdminUpdated, 1),
+                    action(accounts.charlie, ActionKind::FreezeUpdated, 1)
+                ]
+            );
+            // The limit keeps the most recent ones
+            assert_eq!(
+                InkGroupSimple::admin_actions(&contract, 2),
+                vec![
+                    action(accounts.alice, ActionKind::AdminUpdated, 1),
+                    action(accounts.charlie, ActionKind::FreezeUpdated, 1)
+                ]
+            );
+            // A failed action is not recorded
+            set_caller(accounts.alice);
+            assert!(InkGroupSimple::set_max_batch_size(&mut contract, 10).is_err());
+            assert_eq!(InkGroupSimple::admin_actions(&contract, u32::MAX).len(), 6);
+            set_caller(accounts.charlie);
+            for size in 0..MAX_ADMIN_ACTIONS as u32 {
+                InkGroupSimple::set_max_batch_size(&mut contract, size + 1).unwrap();
+            }
+            let actions = InkGroupSimple::admin_actions(&contract, u32::MAX);
+            assert_eq!(actions.len(), MAX_ADMIN_ACTIONS);
+            assert!(actions
+                .iter()
+                .all(|action| action.kind == ActionKind::ConfigUpdated));
+        }
+
+        #[ink::test]
+        /// The ties at the boundary of the committee are broken by address
+        fn committee_works() {
+            let accounts = default_accounts();
+            // Stored in the reverse order of the addresses
+            let members = vec![
+                Member {
+                    addr: accounts.django,
+                    weight: 2,
+                },
+                Member {
+                    addr: accounts.charlie,
+                    weight: 2,
+                },
+                Member {
+                    addr: accounts.bob,
+                    weight: 5,
+                },
+                Member {
+                    addr: accounts.alice,
+                    weight: 1,
+                },
+            ];
+            set_caller(accounts.alice);
+            let contract = InkGroupSimple::try_new(None, members).unwrap();
+            assert_eq!(
+                InkGroupSimple::committee(&contract, 2),
+                vec![accounts.bob, accounts.charlie]
+            );
+            assert_eq!(
+                InkGroupSimple::committee(&contract, 3),
+                vec![accounts.bob, accounts.charlie, accounts.django]
+            );
+            assert_eq!(InkGroupSimple::committee(&contract, 10).len(), 4);
+            assert_eq!(InkGroupSimple::committee(&contract, 0), vec![]);
+        }
+
+        #[ink::test]
+        /// Only a member at or above the threshold passes
+        fn caller_has_weight_works() {
+            let accounts = default_accounts();
+            let mut contract = build_contract();
+            InkGroupSimple::update_member_weight(&mut contract, accounts.alice, 5).unwrap();
+            assert!(InkGroupSimple::caller_has_weight(&contract, 4));
+            assert!(InkGroupSimple::caller_has_weight(&contract, 5));
+            assert!(!InkGroupSimple::caller_has_weight(&contract, 6));
+            set_caller(accounts.charlie);
+            assert!(!InkGroupSimple::caller_has_weight(&contract, 0));
+        }
+
+        #[ink::test]
+        /// The genesis hash covers the construction parameters only
+        fn genesis_hash_works() {
             let accounts = default_accounts();
             let alice_member = Member {
                 addr: accounts.alice,
@@ -303,131 +6122,409 @@ mod ink_group_simple {
             };
             let bob_member = Member {
                 addr: accounts.bob,
-                weight: 1,
+                weight: 2,
             };
-            let charlie_member = Member {
-                addr: accounts.charlie,
-                weight: 1,
+            set_caller(accounts.alice);
+            let mut contract =
+                InkGroupSimple::try_new(None, vec![bob_member, alice_member]).unwrap();
+            let genesis_hash = InkGroupSimple::genesis_hash(&contract);
+            assert_eq!(
+                genesis_hash,
+                blake2x256(&scale::Encode::encode(&(
+                    accounts.alice,
+                    vec![alice_member, bob_member],
+                    GroupConfig::default()
+                )))
+            );
+            // Stable across mutations (the contracts share the storage off-chain, so it's checked
+            // before constructing the others)
+            InkGroupSimple::update_member_weight(&mut contract, accounts.bob, 5).unwrap();
+            InkGroupSimple::set_total_weight_cap(&mut contract, Some(10)).unwrap();
+            InkGroupSimple::update_admin(&mut contract, accounts.charlie).unwrap();
+            assert_eq!(InkGroupSimple::genesis_hash(&contract), genesis_hash);
+            // The order of the initial members doesn't matter
+            let reordered = InkGroupSimple::try_new(None, vec![alice_member, bob_member]).unwrap();
+            assert_eq!(InkGroupSimple::genesis_hash(&reordered), genesis_hash);
+            // The configuration does
+            let config = GroupConfig {
+                allow_self_leave: true,
+                ..Default::default()
             };
-            let members = vec![alice_member, bob_member];
-            let contract = build_contract();
-            let response = InkGroupSimple::get_members(&contract).unwrap();
-            assert_eq!(response, members);
-            assert!(!response.contains(&charlie_member));
+            let configured =
+                InkGroupSimple::try_new_with_config(None, vec![alice_member, bob_member], config)
+                    .unwrap();
+            assert_ne!(InkGroupSimple::genesis_hash(&configured), genesis_hash);
         }
 
         #[ink::test]
-        /// Get member info searched by address
-        fn get_member_works() {
+        /// The storage estimate grows by the size of a member for every member
+        fn storage_estimate_works() {
             let accounts = default_accounts();
-            let alice_member = Member {
-                addr: accounts.alice,
-                weight: 1,
+            let mut contract = build_contract();
+            assert_eq!(
+                InkGroupSimple::storage_estimate(&contract),
+                2 * 40 + STORAGE_OVERHEAD_BYTES
+            );
+            InkGroupSimple::update_members(
+                &mut contract,
+                vec![Member {
+                    addr: accounts.charlie,
+                    weight: 1,
+                }],
+                vec![],
+            )
+            .unwrap();
+            assert_eq!(
+                InkGroupSimple::storage_estimate(&contract),
+                3 * 40 + STORAGE_OVERHEAD_BYTES
+            );
+        }
+
+        #[ink::test]
+        /// The membership is mutable unless frozen
+        fn membership_mutable_works() {
+            let mut contract = build_contract();
+            assert!(InkGroupSimple::membership_mutable(&contract));
+            InkGroupSimple::freeze_membership(&mut contract).unwrap();
+            assert!(!InkGroupSimple::membership_mutable(&contract));
+            InkGroupSimple::unfreeze_membership(&mut contract).unwrap();
+            assert!(InkGroupSimple::membership_mutable(&contract));
+        }
+
+        #[ink::test]
+        /// The median member has the median weight, for odd and even member counts
+        fn median_member_works() {
+            let accounts = default_accounts();
+            let members = vec![
+                Member {
+                    addr: accounts.alice,
+                    weight: 100,
+                },
+                Member {
+                    addr: accounts.bob,
+                    weight: 2,
+                },
+                Member {
+                    addr: accounts.charlie,
+                    weight: 3,
+                },
+            ];
+            set_caller(accounts.alice);
+            let mut contract = InkGroupSimple::try_new(None, members).unwrap();
+            assert_eq!(
+                InkGroupSimple::median_member(&contract),
+                Ok(accounts.charlie)
+            );
+            // Lower middle of 2, 3, 3, 100, the tie broken by address
+            InkGroupSimple::update_members(
+                &mut contract,
+                vec![Member {
+                    addr: accounts.django,
+                    weight: 3,
+                }],
+                vec![],
+            )
+            .unwrap();
+            let median = InkGroupSimple::median_member(&contract).unwrap();
+            assert_eq!(median, accounts.charlie);
+            assert_eq!(
+                InkGroupSimple::get_member(&contract, median).map(|member| member.weight),
+                InkGroupSimple::median_weight(&contract)
+            );
+        }
+
+        #[ink::test]
+        /// No member can exceed the configured share of the total voting power
+        fn max_member_percent_works() {
+            let accounts = default_accounts();
+            let members = vec![
+                Member {
+                    addr: accounts.alice,
+                    weight: 40,
+                },
+                Member {
+                    addr: accounts.bob,
+                    weight: 30,
+                },
+                Member {
+                    addr: accounts.charlie,
+                    weight: 30,
+                },
+            ];
+            let config = GroupConfig {
+                max_member_percent: Some(50),
+                ..Default::default()
             };
-            let contract = build_contract();
-            let response = InkGroupSimple::get_member(&contract, accounts.alice).unwrap();
-            assert_eq!(response, alice_member);
-            let err_response = InkGroupSimple::get_member(&contract, accounts.eve).unwrap_err();
-            assert_eq!(err_response, InkGroupError::NoMember {});
+            set_caller(accounts.alice);
+            assert_eq!(
+                InkGroupSimple::try_new_with_config(
+                    None,
+                    members.clone(),
+                    GroupConfig {
+                        max_member_percent: Some(101),
+                        ..Default::default()
+                    }
+                )
+                .unwrap_err(),
+                ContractError::InkGroup(InkGroupError::InvalidPercent {})
+            );
+            let mut contract = InkGroupSimple::try_new_with_config(None, members, config).unwrap();
+            // 61 of 121 is above 50%
+            assert_eq!(
+                InkGroupSimple::update_member_weight(&mut contract, accounts.alice, 61),
+                Err(InkGroupError::ConcentrationExceeded {
+                    member: accounts.alice,
+                    percent: 50
+                })
+            );
+            assert_eq!(InkGroupSimple::get_total_weight(&contract), 100);
+            // Exactly 50% is accepted
+            InkGroupSimple::update_member_weight(&mut contract, accounts.alice, 60).unwrap();
+            // Removing a member lowers the total under the others
+            assert_eq!(
+                InkGroupSimple::update_members(&mut contract, vec![], vec![accounts.charlie]),
+                Err(InkGroupError::ConcentrationExceeded {
+                    member: accounts.alice,
+                    percent: 50
+                })
+            );
+            assert_eq!(InkGroupSimple::get_members(&contract).unwrap().len(), 3);
         }
 
         #[ink::test]
-        /// Get total voting power
-        fn get_total_weight_works() {
-            let contract = build_contract();
-            let response = InkGroupSimple::get_total_weight(&contract);
-            assert_eq!(response, 2);
+        /// No removal path can push a remaining member over the share of the total voting power
+        fn max_member_percent_on_removal_works() {
+            let accounts = default_accounts();
+            let members = vec![
+                Member {
+                    addr: accounts.alice,
+                    weight: 50,
+                },
+                Member {
+                    addr: accounts.bob,
+                    weight: 25,
+                },
+                Member {
+                    addr: accounts.charlie,
+                    weight: 25,
+                },
+            ];
+            let config = GroupConfig {
+                max_member_percent: Some(50),
+                allow_self_leave: true,
+                ..Default::default()
+            };
+            set_caller(accounts.alice);
+            let mut contract = InkGroupSimple::try_new_with_config(None, members, config).unwrap();
+            let exceeded = || InkGroupError::ConcentrationExceeded {
+                member: accounts.alice,
+                percent: 50,
+            };
+            // 50 of 75 once charlie leaves
+            set_caller(accounts.charlie);
+            assert_eq!(InkGroupSimple::leave(&mut contract), Err(exceeded()));
+            set_caller(accounts.alice);
+            assert_eq!(
+                InkGroupSimple::prune_below_weight(&mut contract, 26),
+                Err(exceeded())
+            );
+            // Merging a duplicate of alice raises her share
+            contract.members.push(Member {
+                addr: accounts.alice,
+                weight: 10,
+            });
+            contract.total_voting_power.add_weight(10).unwrap();
+            assert_eq!(
+                InkGroupSimple::dedup_members(&mut contract),
+                Err(exceeded())
+            );
+            contract.members.pop();
+            contract.total_voting_power.sub_weight(10).unwrap();
+            assert_eq!(InkGroupSimple::get_members(&contract).unwrap().len(), 3);
+            assert_eq!(InkGroupSimple::get_total_weight(&contract), 100);
         }
 
         #[ink::test]
-        /// Update admin
-        fn update_admin_works() {
+        /// The quorum is unreachable without voting power, unless the threshold is zero
+        fn quorum_reachable_works() {
+            let accounts = default_accounts();
+            let mut contract = build_contract();
+            assert_eq!(InkGroupSimple::quorum_reachable(&contract, 100), Ok(true));
+            assert_eq!(
+                InkGroupSimple::quorum_reachable(&contract, 101),
+                Err(InkGroupError::InvalidPercent {})
+            );
+            let zero_weight_members = [accounts.alice, accounts.bob]
+                .iter()
+                .map(|&addr| Member { addr, weight: 0 })
+                .collect();
+            InkGroupSimple::update_members(&mut contract, zero_weight_members, vec![]).unwrap();
+            assert_eq!(InkGroupSimple::quorum_reachable(&contract, 1), Ok(false));
+            assert_eq!(InkGroupSimple::quorum_reachable(&contract, 0), Ok(true));
+        }
+
+        #[ink::test]
+        /// Without a pending admin no account is the pending admin
+        fn am_i_pending_admin_works() {
             let accounts = default_accounts();
             let mut contract = build_contract();
-            set_caller(accounts.bob);
-            let err_response =
-                InkGroupSimple::update_admin(&mut contract, accounts.bob).unwrap_err();
-            assert_eq!(err_response, InkGroupError::Unauthorized {});
-            set_caller(accounts.alice);
             InkGroupSimple::update_admin(&mut contract, accounts.bob).unwrap();
-            assert_eq!(contract.admin.get().unwrap(), accounts.bob);
-            let emittend_events: Vec<EmittedEvent> = ink::env::test::recorded_events().collect();
-            let decoded_events = decode_events(emittend_events);
-            if let Event::AdminUpdate(AdminUpdate {
-                old_admin,
-                new_admin,
-            }) = decoded_events[2]
-            {
-                assert_eq!(old_admin, accounts.alice);
-                assert_eq!(new_admin, accounts.bob);
-            } else {
-                panic!("encountered unexpected event kind: expected a MemberAddition event")
+            for account in [accounts.bob, accounts.alice, accounts.django] {
+                set_caller(account);
+                assert!(!InkGroupSimple::am_i_pending_admin(&contract));
             }
         }
 
         #[ink::test]
-        /// Update members
-        fn update_members_works() {
+        /// An inconsistent patch is rejected as a whole, a consistent one applied at once
+        fn update_config_works() {
             let accounts = default_accounts();
             let mut contract = build_contract();
+            let patch = GroupConfigPatch {
+                max_batch_size: Some(Some(5)),
+                max_total_weight: Some(Some(10)),
+                activation_threshold: Some(Some(20)),
+                ..Default::default()
+            };
             set_caller(accounts.bob);
-            let err_response =
-                InkGroupSimple::update_admin(&mut contract, accounts.bob).unwrap_err();
-            assert_eq!(err_response, InkGroupError::Unauthorized {});
+            assert_eq!(
+                InkGroupSimple::update_config(&mut contract, patch),
+                Err(InkGroupError::Unauthorized {})
+            );
             set_caller(accounts.alice);
-            let update_alice = Member {
-                addr: accounts.alice,
-                weight: 2,
-            };
-            let bob_member = Member {
-                addr: accounts.bob,
-                weight: 1,
-            };
-            let charlie_member = Member {
-                addr: accounts.charlie,
-                weight: 1,
+            // The threshold can't be above the cap: nothing is applied
+            assert_eq!(
+                InkGroupSimple::update_config(&mut contract, patch),
+                Err(InkGroupError::InvalidConfig {})
+            );
+            assert_eq!(
+                InkGroupSimple::get_config(&contract),
+                GroupConfig::default()
+            );
+            // Limits contradicting the current members
+            let patch = GroupConfigPatch {
+                max_batch_size: Some(Some(5)),
+                max_members: Some(Some(1)),
+                ..Default::default()
             };
-            InkGroupSimple::update_members(&mut contract, vec![update_alice], vec![]).unwrap();
-            let result = InkGroupSimple::get_member(&contract, accounts.alice).unwrap();
-            let total_voting_power = InkGroupSimple::get_total_weight(&contract);
-            assert_eq!(result.weight, 2);
-            assert_eq!(total_voting_power, 3);
-            InkGroupSimple::update_members(&mut contract, vec![charlie_member], vec![]).unwrap();
-            let result = InkGroupSimple::get_members(&contract).unwrap();
-            let total_voting_power = InkGroupSimple::get_total_weight(&contract);
-            assert_eq!(result.len(), 3);
-            assert_eq!(total_voting_power, 4);
-            InkGroupSimple::update_members(&mut contract, vec![], vec![accounts.alice]).unwrap();
-            let result = InkGroupSimple::get_members(&contract).unwrap();
-            let total_voting_power = InkGroupSimple::get_total_weight(&contract);
-            assert_eq!(result.len(), 2);
-            assert_eq!(total_voting_power, 2);
-            let err_response =
-                InkGroupSimple::update_members(&mut contract, vec![bob_member, bob_member], vec![])
-                    .unwrap_err();
             assert_eq!(
-                err_response,
-                InkGroupError::DuplicateMember {
-                    member: accounts.bob
-                }
+                InkGroupSimple::update_config(&mut contract, patch),
+                Err(InkGroupError::TooManyMembers { max: 1 })
             );
-            let emittend_events: Vec<EmittedEvent> = ink::env::test::recorded_events().collect();
-            let decoded_events = decode_events(emittend_events);
-            if let Event::MemberUpdate(MemberUpdate { member }) = decoded_events[2] {
-                assert_eq!(member, accounts.alice);
-            } else {
-                panic!("encountered unexpected event kind: expected a MemberAddition event")
-            }
-            if let Event::MemberAddition(MemberAddition { member }) = decoded_events[3] {
-                assert_eq!(member, accounts.charlie);
-            } else {
-                panic!("encountered unexpected event kind: expected a MemberAddition event")
-            }
-            if let Event::MemberRemoval(MemberRemoval { member }) = decoded_events[4] {
-                assert_eq!(member, accounts.alice);
+            assert_eq!(
+                InkGroupSimple::get_config(&contract),
+                GroupConfig::default()
+            );
+            let emitted_events = test::recorded_events().count();
+            let patch = GroupConfigPatch {
+                max_batch_size: Some(Some(5)),
+                max_total_weight: Some(Some(10)),
+                activation_threshold: Some(Some(2)),
+                ..Default::default()
+            };
+            InkGroupSimple::update_config(&mut contract, patch).unwrap();
+            let config = InkGroupSimple::get_config(&contract);
+            assert_eq!(
+                config,
+                GroupConfig {
+                    max_batch_size: Some(5),
+                    max_total_weight: Some(10),
+                    activation_threshold: Some(2),
+                    ..Default::default()
+                }
+            );
+            assert_eq!(InkGroupSimple::cap_history(&contract).len(), 1);
+            let emitted_events = test::recorded_events().skip(emitted_events).collect();
+            let decoded_events = decode_events(emitted_events);
+            assert_eq!(decoded_events.len(), 3);
+            assert!(matches!(
+                decoded_events[1],
+                Event::ThresholdReached(ThresholdReached { threshold: 2 })
+            ));
+            if let Event::ConfigUpdated(ConfigUpdated { old, new }) = decoded_events[2] {
+                assert_eq!(old, GroupConfig::default());
+                assert_eq!(new, config);
             } else {
-                panic!("encountered unexpected event kind: expected a MemberAddition event")
+                panic!("encountered unexpected event kind: expected a ConfigUpdated event")
             }
+            // `Some(None)` removes a limit, `None` keeps it
+            let patch = GroupConfigPatch {
+                max_total_weight: Some(None),
+                ..Default::default()
+            };
+            InkGroupSimple::update_config(&mut contract, patch).unwrap();
+            assert_eq!(InkGroupSimple::get_config(&contract).max_total_weight, None);
+            assert_eq!(
+                InkGroupSimple::get_config(&contract).max_batch_size,
+                Some(5)
+            );
+            // Raising the threshold above the total loses the reached one
+            let emitted_events = test::recorded_events().count();
+            let patch = GroupConfigPatch {
+                activation_threshold: Some(Some(5)),
+                ..Default::default()
+            };
+            InkGroupSimple::update_config(&mut contract, patch).unwrap();
+            let emitted_events = test::recorded_events().skip(emitted_events).collect();
+            let decoded_events = decode_events(emitted_events);
+            assert_eq!(decoded_events.len(), 2);
+            assert!(matches!(
+                decoded_events[0],
+                Event::ThresholdLost(ThresholdLost { threshold: 2 })
+            ));
+            assert!(matches!(decoded_events[1], Event::ConfigUpdated(_)));
+        }
+
+        #[ink::test]
+        /// Compact groups reject the weights above `u32::MAX` but sum the total as u64
+        fn compact_weights_works() {
+            let accounts = default_accounts();
+            let config = GroupConfig {
+                compact_weights: true,
+                ..Default::default()
+            };
+            let too_large = Member {
+                addr: accounts.alice,
+                weight: u64::from(u32::MAX) + 1,
+            };
+            set_caller(accounts.alice);
+            assert_eq!(
+                InkGroupSimple::try_new_with_config(None, vec![too_large], config).unwrap_err(),
+                ContractError::InkGroup(InkGroupError::WeightTooLarge {})
+            );
+            let members = vec![
+                Member {
+                    addr: accounts.alice,
+                    weight: u64::from(u32::MAX),
+                },
+                Member {
+                    addr: accounts.bob,
+                    weight: u64::from(u32::MAX),
+                },
+            ];
+            let mut contract = InkGroupSimple::try_new_with_config(None, members, config).unwrap();
+            assert_eq!(
+                InkGroupSimple::get_total_weight(&contract),
+                2 * u64::from(u32::MAX)
+            );
+            let too_large = Member {
+                addr: accounts.charlie,
+                weight: u64::from(u32::MAX) + 1,
+            };
+            assert_eq!(
+                InkGroupSimple::update_members(&mut contract, vec![too_large], vec![]),
+                Err(InkGroupError::WeightTooLarge {})
+            );
+            assert_eq!(InkGroupSimple::get_members(&contract).unwrap().len(), 2);
+            // The members are stored with 4-byte weights
+            assert_eq!(
+                InkGroupSimple::storage_estimate(&contract),
+                2 * 36 + STORAGE_OVERHEAD_BYTES
+            );
+            let encoded = scale::Encode::encode(&contract.members);
+            let decoded: MemberList = scale::Decode::decode(&mut &encoded[..]).unwrap();
+            assert_eq!(decoded, contract.members);
+            assert_eq!(encoded.len(), 1 + 1 + 2 * 36);
         }
     }
 
@@ -436,6 +6533,7 @@ mod ink_group_simple {
 
         use super::*;
         use ink_e2e::build_message;
+        use ink_group_mock::InkGroupMockRef;
         type E2EResult<T> = std::result::Result<T, Box<dyn std::error::Error>>;
 
         #[ink_e2e::test]
@@ -485,5 +6583,454 @@ mod ink_group_simple {
 
             Ok(())
         }
+
+        #[ink_e2e::test]
+        async fn e2e_code_hash_works(mut client: ink_e2e::Client<C, E>) -> E2EResult<()> {
+            let member = Member {
+                addr: ink_e2e::account_id(ink_e2e::AccountKeyring::Alice),
+                weight: 1,
+            };
+            let constructor = InkGroupSimpleRef::try_new(None, vec![member]);
+            let contract_addr = client
+                .instantiate("ink-group-simple", &ink_e2e::alice(), constructor, 0, None)
+                .await
+                .expect("Instantiate failed")
+                .account_id;
+
+            let code_hash = build_message::<InkGroupSimpleRef>(contract_addr.clone())
+                .call(|ink_group_simple| ink_group_simple.code_hash());
+
+            let result = client
+                .call_dry_run(&ink_e2e::alice(), &code_hash, 0, None)
+                .await;
+
+            assert_ne!(result.return_value(), Hash::default());
+
+            Ok(())
+        }
+
+        #[ink_e2e::test]
+        async fn e2e_spin_off_works(mut client: ink_e2e::Client<C, E>) -> E2EResult<()> {
+            let alice_member = Member {
+                addr: ink_e2e::account_id(ink_e2e::AccountKeyring::Alice),
+                weight: 1,
+            };
+            let bob_member = Member {
+                addr: ink_e2e::account_id(ink_e2e::AccountKeyring::Bob),
+                weight: 2,
+            };
+            let constructor = InkGroupSimpleRef::try_new(None, vec![alice_member, bob_member]);
+            let contract_addr = client
+                .instantiate("ink-group-simple", &ink_e2e::alice(), constructor, 0, None)
+                .await
+                .expect("Instantiate failed")
+                .account_id;
+
+            // The new group is instantiated from the uploaded code of this one
+            let code_hash = build_message::<InkGroupSimpleRef>(contract_addr.clone())
+                .call(|ink_group_simple| ink_group_simple.code_hash());
+            let code_hash = client
+                .call_dry_run(&ink_e2e::alice(), &code_hash, 0, None)
+                .await
+                .return_value();
+
+            let spin_off = build_message::<InkGroupSimpleRef>(contract_addr.clone()).call(
+                |ink_group_simple| ink_group_simple.spin_off(vec![bob_member.addr], code_hash),
+            );
+            let new_group_addr = client
+                .call(&ink_e2e::alice(), spin_off, 0, None)
+                .await
+                .expect("spin_off failed")
+                .return_value()
+                .unwrap();
+
+            let get_members = build_message::<InkGroupSimpleRef>(contract_addr.clone())
+                .call(|ink_group_simple| ink_group_simple.get_members());
+            let result = client
+                .call_dry_run(&ink_e2e::alice(), &get_members, 0, None)
+                .await;
+            assert_eq!(result.return_value(), Ok(vec![alice_member]));
+
+            let get_members = build_message::<InkGroupSimpleRef>(new_group_addr.clone())
+                .call(|ink_group_simple| ink_group_simple.get_members());
+            let result = client
+                .call_dry_run(&ink_e2e::alice(), &get_members, 0, None)
+                .await;
+            assert_eq!(result.return_value(), Ok(vec![bob_member]));
+
+            let get_admin = build_message::<InkGroupSimpleRef>(new_group_addr.clone())
+                .call(|ink_group_simple| ink_group_simple.get_admin());
+            let result = client
+                .call_dry_run(&ink_e2e::alice(), &get_admin, 0, None)
+                .await;
+            assert_eq!(result.return_value(), Ok(alice_member.addr));
+
+            Ok(())
+        }
+
+        #[ink_e2e::test]
+        async fn e2e_remove_hook_works(mut client: ink_e2e::Client<C, E>) -> E2EResult<()> {
+            let alice_member = Member {
+                addr: ink_e2e::account_id(ink_e2e::AccountKeyring::Alice),
+                weight: 1,
+            };
+            let bob_member = Member {
+                addr: ink_e2e::account_id(ink_e2e::AccountKeyring::Bob),
+                weight: 2,
+            };
+            let charlie_member = Member {
+                addr: ink_e2e::account_id(ink_e2e::AccountKeyring::Charlie),
+                weight: 3,
+            };
+            let constructor =
+                InkGroupSimpleRef::try_new(None, vec![alice_member, bob_member, charlie_member]);
+            let contract_addr = client
+                .instantiate("ink-group-simple", &ink_e2e::alice(), constructor, 0, None)
+                .await
+                .expect("Instantiate failed")
+                .account_id;
+            let hook_addr = client
+                .instantiate(
+                    "ink-group-mock",
+                    &ink_e2e::alice(),
+                    InkGroupMockRef::new(),
+                    0,
+                    None,
+                )
+                .await
+                .expect("Instantiate failed")
+                .account_id;
+
+            let set_remove_hook = build_message::<InkGroupSimpleRef>(contract_addr.clone())
+                .call(|ink_group_simple| ink_group_simple.set_remove_hook(Some(hook_addr.clone())));
+            client
+                .call(&ink_e2e::alice(), set_remove_hook, 0, None)
+                .await
+                .expect("set_remove_hook failed");
+
+            // The hook is notified of the removal with the weight of the member
+            let remove_bob = build_message::<InkGroupSimpleRef>(contract_addr.clone()).call(
+                |ink_group_simple| ink_group_simple.update_members(vec![], vec![bob_member.addr]),
+            );
+            client
+                .call(&ink_e2e::alice(), remove_bob, 0, None)
+                .await
+                .expect("update_members failed");
+            let removed = build_message::<InkGroupMockRef>(hook_addr.clone())
+                .call(|ink_group_mock| ink_group_mock.removed());
+            let result = client
+                .call_dry_run(&ink_e2e::alice(), &removed, 0, None)
+                .await;
+            assert_eq!(result.return_value(), vec![(bob_member.addr, 2)]);
+
+            // A reverting hook fails the removal
+            let set_reject = build_message::<InkGroupMockRef>(hook_addr.clone())
+                .call(|ink_group_mock| ink_group_mock.set_reject(true));
+            client
+                .call(&ink_e2e::alice(), set_reject, 0, None)
+                .await
+                .expect("set_reject failed");
+            let remove_charlie = build_message::<InkGroupSimpleRef>(contract_addr.clone()).call(
+                |ink_group_simple| {
+                    ink_group_simple.update_members(vec![], vec![charlie_member.addr])
+                },
+            );
+            assert!(client
+                .call(&ink_e2e::alice(), remove_charlie, 0, None)
+                .await
+                .is_err());
+            let get_member = build_message::<InkGroupSimpleRef>(contract_addr.clone())
+                .call(|ink_group_simple| ink_group_simple.get_member(charlie_member.addr));
+            let result = client
+                .call_dry_run(&ink_e2e::alice(), &get_member, 0, None)
+                .await;
+            assert_eq!(result.return_value(), Ok(charlie_member));
+
+            Ok(())
+        }
+
+        #[ink_e2e::test]
+        async fn e2e_upgrade_unknown_code_hash_fails(
+            mut client: ink_e2e::Client<C, E>,
+        ) -> E2EResult<()> {
+            let alice_member = Member {
+                addr: ink_e2e::account_id(ink_e2e::AccountKeyring::Alice),
+                weight: 1,
+            };
+            let constructor = InkGroupSimpleRef::try_new(None, vec![alice_member]);
+            let contract_addr = client
+                .instantiate("ink-group-simple", &ink_e2e::alice(), constructor, 0, None)
+                .await
+                .expect("Instantiate failed")
+                .account_id;
+
+            // No code is uploaded with this hash
+            let upgrade = build_message::<InkGroupSimpleRef>(contract_addr.clone())
+                .call(|ink_group_simple| ink_group_simple.upgrade(Hash::from([1; 32])));
+            let result = client
+                .call_dry_run(&ink_e2e::alice(), &upgrade, 0, None)
+                .await;
+            assert_eq!(
+                result.return_value(),
+                Err(InkGroupError::UnknownCodeHash {})
+            );
+
+            Ok(())
+        }
+
+        #[ink_e2e::test]
+        async fn e2e_notify_admin_change_works(mut client: ink_e2e::Client<C, E>) -> E2EResult<()> {
+            let alice_member = Member {
+                addr: ink_e2e::account_id(ink_e2e::AccountKeyring::Alice),
+                weight: 1,
+            };
+            let config = GroupConfig {
+                notify_admin_change: true,
+                ..Default::default()
+            };
+            let constructor =
+                InkGroupSimpleRef::try_new_with_config(None, vec![alice_member], config);
+            let contract_addr = client
+                .instantiate("ink-group-simple", &ink_e2e::alice(), constructor, 0, None)
+                .await
+                .expect("Instantiate failed")
+                .account_id;
+            let admin_addr = client
+                .instantiate(
+                    "ink-group-mock",
+                    &ink_e2e::alice(),
+                    InkGroupMockRef::new(),
+                    0,
+                    None,
+                )
+                .await
+                .expect("Instantiate failed")
+                .account_id;
+
+            // Only the new admin is a contract, so only it is notified
+            let update_admin = build_message::<InkGroupSimpleRef>(contract_addr.clone())
+                .call(|ink_group_simple| ink_group_simple.update_admin(admin_addr.clone()));
+            client
+                .call(&ink_e2e::alice(), update_admin, 0, None)
+                .await
+                .expect("update_admin failed");
+            let admin_changes = build_message::<InkGroupMockRef>(admin_addr.clone())
+                .call(|ink_group_mock| ink_group_mock.admin_changes());
+            let result = client
+                .call_dry_run(&ink_e2e::alice(), &admin_changes, 0, None)
+                .await;
+            assert_eq!(result.return_value(), vec![(alice_member.addr, admin_addr)]);
+
+            Ok(())
+        }
+
+        #[ink_e2e::test]
+        async fn e2e_client_unique_member_count_works(
+            mut client: ink_e2e::Client<C, E>,
+        ) -> E2EResult<()> {
+            let alice_member = Member {
+                addr: ink_e2e::account_id(ink_e2e::AccountKeyring::Alice),
+                weight: 1,
+            };
+            let bob_member = Member {
+                addr: ink_e2e::account_id(ink_e2e::AccountKeyring::Bob),
+                weight: 2,
+            };
+            let charlie_member = Member {
+                addr: ink_e2e::account_id(ink_e2e::AccountKeyring::Charlie),
+                weight: 3,
+            };
+            let mut groups = Vec::new();
+            for members in [
+                vec![alice_member, bob_member],
+                vec![bob_member, charlie_member],
+            ] {
+                let constructor = InkGroupSimpleRef::try_new(None, members);
+                let group_addr = client
+                    .instantiate("ink-group-simple", &ink_e2e::alice(), constructor, 0, None)
+                    .await
+                    .expect("Instantiate failed")
+                    .account_id;
+                groups.push(group_addr);
+            }
+            let reader_addr = client
+                .instantiate(
+                    "ink-group-mock",
+                    &ink_e2e::alice(),
+                    InkGroupMockRef::new(),
+                    0,
+                    None,
+                )
+                .await
+                .expect("Instantiate failed")
+                .account_id;
+
+            // Bob is a member of both groups but is counted once
+            let unique_member_count = build_message::<InkGroupMockRef>(reader_addr.clone())
+                .call(|ink_group_mock| ink_group_mock.unique_member_count(groups.clone()));
+            let result = client
+                .call_dry_run(&ink_e2e::alice(), &unique_member_count, 0, None)
+                .await;
+            assert_eq!(result.return_value(), Ok(3));
+
+            Ok(())
+        }
+
+        // The e2e client builds the contract with its default features, run with
+        // `cargo test --features e2e-tests,cross-contract` and a contract built with
+        // `cross-contract`.
+        #[cfg(feature = "cross-contract")]
+        #[ink_e2e::test]
+        async fn e2e_sync_weights_from_token_works(
+            mut client: ink_e2e::Client<C, E>,
+        ) -> E2EResult<()> {
+            let alice_member = Member {
+                addr: ink_e2e::account_id(ink_e2e::AccountKeyring::Alice),
+                weight: 1,
+            };
+            let bob_member = Member {
+                addr: ink_e2e::account_id(ink_e2e::AccountKeyring::Bob),
+                weight: 2,
+            };
+            let constructor = InkGroupSimpleRef::try_new(None, vec![alice_member, bob_member]);
+            let contract_addr = client
+                .instantiate("ink-group-simple", &ink_e2e::alice(), constructor, 0, None)
+                .await
+                .expect("Instantiate failed")
+                .account_id;
+            let token_addr = client
+                .instantiate(
+                    "ink-group-mock",
+                    &ink_e2e::alice(),
+                    InkGroupMockRef::new(),
+                    0,
+                    None,
+                )
+                .await
+                .expect("Instantiate failed")
+                .account_id;
+            for member in [alice_member, bob_member] {
+                let set_balance =
+                    build_message::<InkGroupMockRef>(token_addr.clone()).call(|ink_group_mock| {
+                        ink_group_mock.set_balance(member.addr, u128::from(member.weight))
+                    });
+                client
+                    .call(&ink_e2e::alice(), set_balance, 0, None)
+                    .await
+                    .expect("set_balance failed");
+            }
+
+            // Balances matching the weights don't change the group
+            let sync = build_message::<InkGroupSimpleRef>(contract_addr.clone())
+                .call(|ink_group_simple| ink_group_simple.sync_weights_from_token(token_addr));
+            client
+                .call(&ink_e2e::alice(), sync, 0, None)
+                .await
+                .expect("sync_weights_from_token failed");
+            let current_epoch = build_message::<InkGroupSimpleRef>(contract_addr.clone())
+                .call(|ink_group_simple| ink_group_simple.current_epoch());
+            let result = client
+                .call_dry_run(&ink_e2e::alice(), &current_epoch, 0, None)
+                .await;
+            assert_eq!(result.return_value(), 0);
+
+            // A changed balance updates the weight and bumps the epoch
+            let set_balance = build_message::<InkGroupMockRef>(token_addr.clone())
+                .call(|ink_group_mock| ink_group_mock.set_balance(bob_member.addr, 5));
+            client
+                .call(&ink_e2e::alice(), set_balance, 0, None)
+                .await
+                .expect("set_balance failed");
+            let sync = build_message::<InkGroupSimpleRef>(contract_addr.clone())
+                .call(|ink_group_simple| ink_group_simple.sync_weights_from_token(token_addr));
+            client
+                .call(&ink_e2e::alice(), sync, 0, None)
+                .await
+                .expect("sync_weights_from_token failed");
+            let result = client
+                .call_dry_run(&ink_e2e::alice(), &current_epoch, 0, None)
+                .await;
+            assert_eq!(result.return_value(), 1);
+            let get_member = build_message::<InkGroupSimpleRef>(contract_addr.clone())
+                .call(|ink_group_simple| ink_group_simple.get_member(bob_member.addr));
+            let result = client
+                .call_dry_run(&ink_e2e::alice(), &get_member, 0, None)
+                .await;
+            assert_eq!(
+                result.return_value(),
+                Ok(Member {
+                    addr: bob_member.addr,
+                    weight: 5
+                })
+            );
+
+            Ok(())
+        }
+
+        #[cfg(feature = "cross-contract")]
+        #[ink_e2e::test]
+        async fn e2e_sync_weights_capped_works(mut client: ink_e2e::Client<C, E>) -> E2EResult<()> {
+            let alice_member = Member {
+                addr: ink_e2e::account_id(ink_e2e::AccountKeyring::Alice),
+                weight: 1,
+            };
+            let bob_member = Member {
+                addr: ink_e2e::account_id(ink_e2e::AccountKeyring::Bob),
+                weight: 2,
+            };
+            let constructor = InkGroupSimpleRef::try_new(None, vec![alice_member, bob_member]);
+            let contract_addr = client
+                .instantiate("ink-group-simple", &ink_e2e::alice(), constructor, 0, None)
+                .await
+                .expect("Instantiate failed")
+                .account_id;
+            let token_addr = client
+                .instantiate(
+                    "ink-group-mock",
+                    &ink_e2e::alice(),
+                    InkGroupMockRef::new(),
+                    0,
+                    None,
+                )
+                .await
+                .expect("Instantiate failed")
+                .account_id;
+            for (addr, balance) in [(alice_member.addr, 100), (bob_member.addr, 3)] {
+                let set_balance = build_message::<InkGroupMockRef>(token_addr.clone())
+                    .call(|ink_group_mock| ink_group_mock.set_balance(addr, balance));
+                client
+                    .call(&ink_e2e::alice(), set_balance, 0, None)
+                    .await
+                    .expect("set_balance failed");
+            }
+
+            // Only the balance above the cap is capped
+            let sync = build_message::<InkGroupSimpleRef>(contract_addr.clone())
+                .call(|ink_group_simple| ink_group_simple.sync_weights_capped(token_addr, 10));
+            client
+                .call(&ink_e2e::alice(), sync, 0, None)
+                .await
+                .expect("sync_weights_capped failed");
+            let get_members = build_message::<InkGroupSimpleRef>(contract_addr.clone())
+                .call(|ink_group_simple| ink_group_simple.get_members());
+            let result = client
+                .call_dry_run(&ink_e2e::alice(), &get_members, 0, None)
+                .await;
+            assert_eq!(
+                result.return_value(),
+                Ok(vec![
+                    Member {
+                        addr: alice_member.addr,
+                        weight: 10
+                    },
+                    Member {
+                        addr: bob_member.addr,
+                        weight: 3
+                    }
+                ])
+            );
+
+            Ok(())
+        }
     }
 }
@@ -1,16 +1,197 @@
+use core::cmp::Ordering;
+use ink::env::hash::{Blake2x256, HashOutput};
+use ink::prelude::{string::ToString, vec::Vec};
+use ink::primitives::AccountId;
 use ink_group::{InkGroupError, Member};
 
+/// Maximum number of boundaries accepted by `weight_buckets`.
+pub const MAX_BUCKET_BOUNDARIES: usize = 32;
+
+/// Maximum number of members accepted by `pivotal_count` (every coalition is enumerated).
+pub const MAX_PIVOTAL_MEMBERS: usize = 12;
+
+/// Maximum number of members accepted by `weight_gini_bps`.
+pub const MAX_GINI_MEMBERS: usize = 1000;
+
+/// Maximum number of accounts accepted by `lookup_weights` and `get_roles`.
+pub const MAX_LOOKUP_ACCOUNTS: usize = 100;
+
+/// Maximum number of membership changes kept by `recent_changes`.
+pub const MAX_CHANGE_RECORDS: usize = 32;
+
+/// Maximum number of admin actions kept by `admin_actions`.
+pub const MAX_ADMIN_ACTIONS: usize = 32;
+
+/// Approximate size in bytes of the storage apart from the members, used by `storage_estimate`.
+pub const STORAGE_OVERHEAD_BYTES: u64 = 256;
+
+/// Hard upper bound of the batch size of the admin updates (`max_batch_size` is clamped to it).
+pub const MAX_BATCH_SIZE: usize = 500;
+
+/// Maximum number of changes kept by `cap_history`, the oldest are evicted first.
+pub const MAX_CAP_HISTORY: usize = 16;
+
 /// Verifies all member addresses are unique.
 pub fn validate_unique_members(members: &[Member]) -> Result<(), InkGroupError> {
-    for (a, b) in members.iter().zip(members.iter().skip(1)) {
-        if a.addr == b.addr {
-            return Err(InkGroupError::DuplicateMember { member: a.addr });
+    let addresses: Vec<AccountId> = members.iter().map(|member| member.addr).collect();
+    validate_unique_addresses(&addresses)
+}
+
+/// Verifies all addresses are unique.
+pub fn validate_unique_addresses(addresses: &[AccountId]) -> Result<(), InkGroupError> {
+    // Sort a copy of the addresses so that duplicates are adjacent wherever they are entered
+    let mut addresses = addresses.to_vec();
+    addresses.sort_unstable();
+    for (a, b) in addresses.iter().zip(addresses.iter().skip(1)) {
+        if a == b {
+            return Err(InkGroupError::DuplicateMember { member: *a });
         }
     }
 
     Ok(())
 }
 
+/// Rescale the weights proportionally so that they sum to `target`, with the largest remainder
+/// method: every member gets the floor of its exact share, then the units left are given one
+/// each to the largest remainders (ties to the first member). Fails with `ZeroTotalWeight` if
+/// all the weights are zero.
+pub fn normalize_weights(members: &[Member], target: u64) -> Result<Vec<Member>, InkGroupError> {
+    // The products of two u64 fit in a u128, so nothing below can overflow
+    let total: u128 = members.iter().map(|member| u128::from(member.weight)).sum();
+    if total == 0 {
+        return Err(InkGroupError::ZeroTotalWeight {});
+    }
+    let mut normalized = Vec::with_capacity(members.len());
+    let mut remainders = Vec::with_capacity(members.len());
+    let mut assigned: u128 = 0;
+    for (index, member) in members.iter().enumerate() {
+        let share = u128::from(member.weight) * u128::from(target);
+        let weight = share / total;
+        assigned += weight;
+        remainders.push((share % total, index));
+        normalized.push(Member {
+            addr: member.addr,
+            weight: u64::try_from(weight).map_err(|_| InkGroupError::WeightOverflow {})?,
+        });
+    }
+    // Fewer units are left than members, as every floor loses less than one
+    let left = u128::from(target) - assigned;
+    remainders.sort_by(|a, b| b.0.cmp(&a.0).then(a.1.cmp(&b.1)));
+    for &(_, index) in remainders.iter().take(left as usize) {
+        normalized[index].weight += 1;
+    }
+    Ok(normalized)
+}
+
+/// Order the members by descending weight, ties by ascending address, so that a ranking is the
+/// same whatever the storage order.
+pub fn rank_order(a: &Member, b: &Member) -> Ordering {
+    b.weight.cmp(&a.weight).then(a.addr.cmp(&b.addr))
+}
+
+/// Verifies no member holds more than `percent` of `total_power`.
+pub fn validate_concentration(
+    members: &[Member],
+    total_power: u64,
+    percent: u32,
+) -> Result<(), InkGroupError> {
+    // Compare `weight * 100 <= total * percent` to avoid rounding the limit
+    let limit = u128::from(total_power) * u128::from(percent);
+    if let Some(member) = members
+        .iter()
+        .find(|member| u128::from(member.weight) * 100 > limit)
+    {
+        return Err(InkGroupError::ConcentrationExceeded {
+            member: member.addr,
+            percent,
+        });
+    }
+    Ok(())
+}
+
+/// Verifies the activation threshold can be reached under the cap on the total voting power.
+pub fn validate_activation_threshold(
+    threshold: Option<u64>,
+    cap: Option<u64>,
+) -> Result<(), InkGroupError> {
+    if let (Some(threshold), Some(cap)) = (threshold, cap) {
+        if threshold > cap {
+            return Err(InkGroupError::InvalidConfig {});
+        }
+    }
+    Ok(())
+}
+
+/// Clamp a maximum batch size between 1 and `MAX_BATCH_SIZE`.
+pub fn clamp_batch_size(size: u32) -> u32 {
+    size.clamp(1, MAX_BATCH_SIZE as u32)
+}
+
+/// Return if the address is the zero address (`[0; 32]`).
+pub fn is_zero_address(addr: &AccountId) -> bool {
+    AsRef::<[u8; 32]>::as_ref(addr) == &[0u8; 32]
+}
+
+/// Convert a token balance to a member weight, balances above `u64::MAX` are capped to
+/// `u64::MAX`.
+#[cfg(feature = "cross-contract")]
+pub fn balance_to_weight(balance: u128) -> u64 {
+    u64::try_from(balance).unwrap_or(u64::MAX)
+}
+
+/// Encode a member as a merkle leaf: the 32-byte address followed by the 8-byte big-endian
+/// weight.
+pub fn member_leaf(member: &Member) -> [u8; 40] {
+    let mut leaf = [0u8; 40];
+    leaf[..32].copy_from_slice(AsRef::<[u8]>::as_ref(&member.addr));
+    leaf[32..].copy_from_slice(&member.weight.to_be_bytes());
+    leaf
+}
+
+/// Append the member to `out` as a CSV row: the `0x` prefixed lowercase hex address, a comma,
+/// the decimal weight and a newline.
+pub fn push_csv_row(out: &mut Vec<u8>, member: &Member) {
+    const HEX: &[u8; 16] = b"0123456789abcdef";
+    out.extend_from_slice(b"0x");
+    for byte in AsRef::<[u8]>::as_ref(&member.addr) {
+        out.push(HEX[usize::from(byte >> 4)]);
+        out.push(HEX[usize::from(byte & 0x0f)]);
+    }
+    out.push(b',');
+    out.extend_from_slice(member.weight.to_string().as_bytes());
+    out.push(b'\n');
+}
+
+/// Compute the merkle root of the leaves: every leaf is hashed with BLAKE2b-256, then each
+/// level hashes the concatenation of the pairs of nodes, an odd last node is carried to the
+/// next level as is. The root of no leaves is `[0; 32]`.
+pub fn merkle_root(leaves: &[[u8; 40]]) -> [u8; 32] {
+    let mut level: Vec<[u8; 32]> = leaves.iter().map(|leaf| blake2x256(leaf)).collect();
+    while level.len() > 1 {
+        level = level
+            .chunks(2)
+            .map(|pair| match pair {
+                [left, right] => {
+                    let mut input = [0u8; 64];
+                    input[..32].copy_from_slice(left);
+                    input[32..].copy_from_slice(right);
+                    blake2x256(&input)
+                }
+                [node] => *node,
+                _ => unreachable!("chunks of at most 2 nodes"),
+            })
+            .collect();
+    }
+    level.first().copied().unwrap_or_default()
+}
+
+/// Hash the input with BLAKE2b-256.
+pub fn blake2x256(input: &[u8]) -> [u8; 32] {
+    let mut output = <Blake2x256 as HashOutput>::Type::default();
+    ink::env::hash_bytes::<Blake2x256>(input, &mut output);
+    output
+}
+
 /// Evaluate `$x:expr` and if not true return `Err($y:expr)`.
 ///
 /// Used as `ensure!(expression_to_ensure, expression_to_return_on_false)`.
@@ -0,0 +1,105 @@
+use core::ops::{Deref, DerefMut};
+use ink::prelude::vec::Vec;
+use ink_group::Member;
+
+/// Members of the group, stored with `u32` weights in compact mode. The members are always
+/// handled with `u64` weights, only the storage encoding changes.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct MemberList {
+    /// Store the weights as `u32`
+    compact: bool,
+    /// Members by storage order
+    members: Vec<Member>,
+}
+
+/// Storage encoding of a `MemberList`
+#[derive(scale::Encode, scale::Decode)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub enum StoredMembers {
+    /// Members with `u64` weights
+    Full(Vec<Member>),
+    /// Members with `u32` weights
+    Compact(Vec<Member<u32>>),
+}
+
+impl MemberList {
+    /// Return an empty member list, compact if `compact`
+    pub fn new(compact: bool) -> Self {
+        Self {
+            compact,
+            members: Vec::new(),
+        }
+    }
+
+    /// Return whether the weights are stored as `u32`
+    pub fn is_compact(&self) -> bool {
+        self.compact
+    }
+}
+
+impl Deref for MemberList {
+    type Target = Vec<Member>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.members
+    }
+}
+
+impl DerefMut for MemberList {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.members
+    }
+}
+
+impl scale::Encode for MemberList {
+    fn encode_to<T: scale::Output + ?Sized>(&self, dest: &mut T) {
+        if self.compact {
+            // Every weight is checked against `u32::MAX` before it is stored
+            let members = self
+                .members
+                .iter()
+                .map(|member| Member {
+                    addr: member.addr,
+                    weight: member.weight as u32,
+                })
+                .collect();
+            StoredMembers::Compact(members).encode_to(dest)
+        } else {
+            StoredMembers::Full(self.members.clone()).encode_to(dest)
+        }
+    }
+}
+
+impl scale::Decode for MemberList {
+    fn decode<I: scale::Input>(input: &mut I) -> Result<Self, scale::Error> {
+        Ok(match StoredMembers::decode(input)? {
+            StoredMembers::Full(members) => Self {
+                compact: false,
+                members,
+            },
+            StoredMembers::Compact(members) => Self {
+                compact: true,
+                members: members.into_iter().map(Member::from).collect(),
+            },
+        })
+    }
+}
+
+#[cfg(feature = "std")]
+impl scale_info::TypeInfo for MemberList {
+    type Identity = StoredMembers;
+
+    fn type_info() -> scale_info::Type {
+        StoredMembers::type_info()
+    }
+}
+
+#[cfg(feature = "std")]
+impl ink::storage::traits::StorageLayout for MemberList {
+    fn layout(key: &ink::primitives::Key) -> ink::metadata::layout::Layout {
+        <StoredMembers as ink::storage::traits::StorageLayout>::layout(key)
+    }
+}
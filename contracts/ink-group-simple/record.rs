@@ -0,0 +1,71 @@
+use ink::primitives::AccountId;
+
+/// Kind of a membership change
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub enum ChangeKind {
+    /// The member was added
+    Added,
+    /// The weight of the member was updated
+    Updated,
+    /// The member was removed
+    Removed,
+}
+
+/// Record of a membership change, mirroring its event
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct ChangeRecord {
+    /// Kind of the change
+    pub kind: ChangeKind,
+    /// Member that changed
+    pub member: AccountId,
+    /// Change of the weight of the member (negative for a removal)
+    pub weight_delta: i128,
+    /// Block of the change
+    pub block: u32,
+}
+
+/// Kind of an admin action
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub enum ActionKind {
+    /// Members added, removed or reweighted
+    MembersUpdated,
+    /// Role labels set
+    RolesUpdated,
+    /// Admin changed
+    AdminUpdated,
+    /// Configuration (caps, batch size, remove hook) changed
+    ConfigUpdated,
+    /// Members locked or unlocked
+    LocksUpdated,
+    /// Membership frozen or unfrozen
+    FreezeUpdated,
+    /// Code of the contract replaced
+    Upgraded,
+}
+
+/// Record of an admin action
+#[derive(Debug, Clone, Copy, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+pub struct ActionRecord {
+    /// Account that performed the action
+    pub actor: AccountId,
+    /// Kind of the action
+    pub kind: ActionKind,
+    /// Block of the action
+    pub block: u32,
+}
@@ -0,0 +1,63 @@
+//! Helpers for contracts reading other groups through cross-contract calls.
+
+use ink::env::call::{build_call, ExecutionInput, Selector};
+use ink::env::DefaultEnvironment;
+use ink::prelude::vec::Vec;
+use ink::primitives::AccountId;
+
+use crate::{error::InkGroupError, selectors::GET_MEMBERS_SELECTOR, storage::Member};
+
+/// Maximum number of groups read by a single helper call
+pub const MAX_CLIENT_GROUPS: usize = 16;
+
+/// Client of `InkGroup` contracts called by selector
+pub struct InkGroupClient;
+
+impl InkGroupClient {
+    /// Return the number of distinct members across `groups` (at most `MAX_CLIENT_GROUPS`).
+    /// Every group is read with one `get_members` call, so the gas grows with the total number
+    /// of members of the groups.
+    pub fn unique_member_count(groups: &[AccountId]) -> Result<u32, InkGroupError> {
+        if groups.len() > MAX_CLIENT_GROUPS {
+            return Err(InkGroupError::InputTooLong {
+                max: MAX_CLIENT_GROUPS as u32,
+            });
+        }
+        let mut addresses = Vec::new();
+        for &group in groups {
+            let members = build_call::<DefaultEnvironment>()
+                .call(group)
+                .gas_limit(0)
+                .exec_input(ExecutionInput::new(Selector::new(GET_MEMBERS_SELECTOR)))
+                .returns::<Result<Vec<Member>, InkGroupError>>()
+                .try_invoke()???;
+            addresses.extend(members.iter().map(|member| member.addr));
+        }
+        Ok(count_distinct(addresses))
+    }
+}
+
+/// Return the number of distinct addresses
+fn count_distinct(mut addresses: Vec<AccountId>) -> u32 {
+    addresses.sort_unstable();
+    addresses.dedup();
+    addresses.len() as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn count_distinct_works() {
+        let group_a = [[0x01; 32], [0x02; 32], [0x03; 32]];
+        let group_b = [[0x03; 32], [0x04; 32], [0x01; 32]];
+        let addresses = group_a
+            .iter()
+            .chain(group_b.iter())
+            .map(|&addr| AccountId::from(addr))
+            .collect();
+        assert_eq!(count_distinct(addresses), 4);
+        assert_eq!(count_distinct(Vec::new()), 0);
+    }
+}
@@ -0,0 +1,17 @@
+use core::fmt;
+
+use ink::primitives::AccountId;
+
+/// Display an `AccountId` as a `0x` prefixed hex string (e.g. in error messages)
+pub(crate) struct AccountHex<'a>(pub &'a AccountId);
+
+impl fmt::Display for AccountHex<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let bytes: &[u8; 32] = self.0.as_ref();
+        f.write_str("0x")?;
+        for byte in bytes {
+            write!(f, "{byte:02x}")?;
+        }
+        Ok(())
+    }
+}
@@ -1,17 +1,175 @@
 use ink::primitives::AccountId;
 use thiserror_no_std::Error;
 
+#[cfg(feature = "std")]
+use crate::display::AccountHex;
+
 #[derive(Error, Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
 #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum InkGroupError {
     #[error("Logic contract error")]
     LogicErr {},
     #[error("Unauthorized")]
     Unauthorized {},
-    #[error("entered duplicate member")]
-    DuplicateMember { member: AccountId },
+    // The address is only formatted off-chain to keep the contracts small
+    #[cfg_attr(feature = "std", error("entered duplicate member {}", AccountHex(.member)))]
+    #[cfg_attr(not(feature = "std"), error("entered duplicate member"))]
+    DuplicateMember {
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_account"))]
+        member: AccountId,
+    },
     #[error("no members entered")]
     ZeroMembers {},
     #[error("member not found")]
     NoMember {},
+    #[error("address prefix longer than 32 bytes")]
+    InvalidPrefix {},
+    #[error("no members to add or remove")]
+    EmptyUpdate {},
+    #[error("total voting power overflow")]
+    WeightOverflow {},
+    #[error("invalid admin address")]
+    InvalidAdmin {},
+    #[error("member weight too large")]
+    WeightTooLarge {},
+    #[error("external call failed with code {code}")]
+    ExternalCallFailed { code: u8 },
+    #[error("invalid range")]
+    InvalidRange {},
+    #[error("total voting power above the cap {cap}")]
+    TotalWeightCapExceeded { cap: u64 },
+    #[error("bucket boundaries not strictly ascending or too many")]
+    InvalidBoundaries {},
+    #[error("member weight above the cap {cap}")]
+    WeightCapExceeded { cap: u64 },
+    #[error("percentage above 100")]
+    InvalidPercent {},
+    #[error("threshold unreachable with the total voting power")]
+    QuorumUnreachable {},
+    #[error("the caller can't change its own weight")]
+    CannotModifyOwnWeight {},
+    #[error("member weight below zero")]
+    WeightUnderflow {},
+    #[error("vectors of different length")]
+    LengthMismatch {},
+    #[error("the update changes nothing")]
+    NoChange {},
+    #[cfg_attr(feature = "std", error("member {} is locked", AccountHex(.member)))]
+    #[cfg_attr(not(feature = "std"), error("member is locked"))]
+    MemberLocked {
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_account"))]
+        member: AccountId,
+    },
+    #[error("more than {max} members")]
+    TooManyMembers { max: u32 },
+    #[error("the admin can't be a member")]
+    AdminCannotBeMember {},
+    #[error("more than {max} entries in the input")]
+    InputTooLong { max: u32 },
+    #[error("the admin must be a contract")]
+    AdminMustBeContract {},
+    #[error("operation forbidden in the current state (reason {reason})")]
+    InvalidState { reason: u8 },
+    #[error("weight change available at block {available_at}")]
+    CooldownActive { available_at: u32 },
+    #[error("total voting power is zero")]
+    ZeroTotalWeight {},
+    #[error("admin change available at block {available_at}")]
+    AdminCooldownActive { available_at: u32 },
+    #[cfg_attr(
+        feature = "std",
+        error("member {} above {percent}% of the total voting power", AccountHex(.member))
+    )]
+    #[cfg_attr(
+        not(feature = "std"),
+        error("member above {percent}% of the total voting power")
+    )]
+    ConcentrationExceeded {
+        #[cfg_attr(feature = "serde", serde(with = "crate::serde_account"))]
+        member: AccountId,
+        percent: u32,
+    },
+    #[error("inconsistent configuration")]
+    InvalidConfig {},
+    #[error("no code uploaded with the code hash")]
+    UnknownCodeHash {},
+}
+
+impl InkGroupError {
+    /// `ExternalCallFailed` code: the callee couldn't dispatch the message (e.g. unknown selector)
+    pub const CALL_DISPATCH_FAILED: u8 = 0;
+    /// `ExternalCallFailed` code: the callee trapped
+    pub const CALL_TRAPPED: u8 = 1;
+    /// `ExternalCallFailed` code: the callee reverted
+    pub const CALL_REVERTED: u8 = 2;
+    /// `ExternalCallFailed` code: the callee is not a contract
+    pub const CALL_NOT_CALLABLE: u8 = 3;
+    /// `ExternalCallFailed` code: the returned value couldn't be decoded
+    pub const CALL_DECODE_FAILED: u8 = 4;
+    /// `ExternalCallFailed` code: any other environment error
+    pub const CALL_OTHER: u8 = u8::MAX;
+    /// `InvalidState` reason: members can't be added or removed while the membership is frozen
+    pub const STATE_MEMBERSHIP_FROZEN: u8 = 0;
+}
+
+/// Translate the environment error of a cross-contract call
+impl From<ink::env::Error> for InkGroupError {
+    fn from(err: ink::env::Error) -> Self {
+        let code = match err {
+            ink::env::Error::CalleeTrapped => Self::CALL_TRAPPED,
+            ink::env::Error::CalleeReverted => Self::CALL_REVERTED,
+            ink::env::Error::NotCallable | ink::env::Error::CodeNotFound => Self::CALL_NOT_CALLABLE,
+            ink::env::Error::Decode(_) => Self::CALL_DECODE_FAILED,
+            _ => Self::CALL_OTHER,
+        };
+        InkGroupError::ExternalCallFailed { code }
+    }
+}
+
+/// Translate the dispatch error of a cross-contract call
+impl From<ink::LangError> for InkGroupError {
+    fn from(_: ink::LangError) -> Self {
+        InkGroupError::ExternalCallFailed {
+            code: Self::CALL_DISPATCH_FAILED,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn duplicate_member_display_works() {
+        let err = InkGroupError::DuplicateMember {
+            member: AccountId::from([0x01; 32]),
+        };
+        assert_eq!(
+            err.to_string(),
+            format!("entered duplicate member 0x{}", "01".repeat(32))
+        );
+    }
+
+    #[test]
+    fn external_call_failed_works() {
+        assert_eq!(
+            InkGroupError::from(ink::env::Error::CalleeReverted),
+            InkGroupError::ExternalCallFailed {
+                code: InkGroupError::CALL_REVERTED
+            }
+        );
+        assert_eq!(
+            InkGroupError::from(ink::env::Error::NotCallable),
+            InkGroupError::ExternalCallFailed {
+                code: InkGroupError::CALL_NOT_CALLABLE
+            }
+        );
+        assert_eq!(
+            InkGroupError::from(ink::LangError::CouldNotReadInput),
+            InkGroupError::ExternalCallFailed {
+                code: InkGroupError::CALL_DISPATCH_FAILED
+            }
+        );
+    }
 }
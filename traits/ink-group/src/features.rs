@@ -0,0 +1,12 @@
+//! Bit flags of the optional capabilities returned by `InkGroup::supported_features`.
+
+/// The implementation emits events on every mutation
+pub const EVENTS: u32 = 1 << 0;
+/// The implementation supports proposals and voting
+pub const VOTING: u32 = 1 << 1;
+/// The implementation supports delegation of voting power
+pub const DELEGATION: u32 = 1 << 2;
+/// The implementation supports suspension of members
+pub const SUSPENSION: u32 = 1 << 3;
+/// The implementation supports timelocked operations
+pub const TIMELOCK: u32 = 1 << 4;
@@ -1,9 +1,21 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
+#[cfg(feature = "cross-contract")]
+mod client;
+#[cfg(feature = "std")]
+mod display;
 mod error;
+pub mod features;
 mod message;
+pub mod selectors;
+#[cfg(feature = "serde")]
+mod serde_account;
 mod storage;
+mod weight;
 
+#[cfg(feature = "cross-contract")]
+pub use crate::client::{InkGroupClient, MAX_CLIENT_GROUPS};
 pub use crate::error::InkGroupError;
 pub use crate::message::InkGroup;
-pub use crate::storage::Member;
+pub use crate::storage::{GroupHealth, Member, Role, SyncPage, UpdateReport};
+pub use crate::weight::{TotalWeight, Weight};
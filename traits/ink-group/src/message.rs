@@ -1,7 +1,10 @@
 use ink::prelude::vec::Vec;
-use ink::primitives::AccountId;
+use ink::primitives::{AccountId, Hash};
 
-use crate::{error::InkGroupError, storage::Member};
+use crate::{
+    error::InkGroupError,
+    storage::{GroupHealth, Member, Role, SyncPage, UpdateReport},
+};
 
 #[ink::trait_definition]
 pub trait InkGroup {
@@ -18,20 +21,307 @@ pub trait InkGroup {
     /// Return a specific member info request by contract address
     fn get_member(&self, member: AccountId) -> Result<Member, InkGroupError>;
 
+    #[ink(message)]
+    /// Return up to `limit` members whose address bytes start with `prefix` (at most 32 bytes).
+    /// It scans every member (O(n)): meant for admin tools, not for hot paths
+    fn find_members_by_prefix(
+        &self,
+        prefix: Vec<u8>,
+        limit: u32,
+    ) -> Result<Vec<Member>, InkGroupError>;
+
     #[ink(message)]
     /// Return the total voting power weight of the grop
     fn get_total_weight(&self) -> u64;
 
+    #[ink(message)]
+    /// Return the block number of the last successful mutation (members, admin or configuration)
+    fn last_mutated_block(&self) -> u32;
+
+    #[ink(message)]
+    /// Return the bit flags (see `features`) of the optional capabilities of the implementation
+    fn supported_features(&self) -> u32;
+
+    #[ink(message)]
+    /// Return the summed weight of the `n` highest weight members (`n` is clamped to the number
+    /// of members)
+    fn top_n_weight(&self, n: u32) -> u64;
+
+    #[ink(message)]
+    /// Return the membership epoch, incremented by one on every membership change
+    fn current_epoch(&self) -> u64;
+
+    #[ink(message)]
+    /// Return if the members of the group are exactly `expected` (order-independent, weights
+    /// must match)
+    fn matches_members(&self, expected: Vec<Member>) -> bool;
+
+    #[ink(message)]
+    /// Return if at least one member has a weight of at least `min` (stops at the first one)
+    fn exists_member_with_weight(&self, min: u64) -> bool;
+
+    #[ink(message)]
+    /// Return the members in `[start, end)` by storage order (error if `start > end` or `end` is
+    /// greater than the number of members)
+    fn members_slice(&self, start: u32, end: u32) -> Result<Vec<Member>, InkGroupError>;
+
+    #[ink(message)]
+    /// Return the actual admin and the pending admin of a two-step transfer (if any, `None` for
+    /// implementations without pending transfers)
+    fn admin_state(&self) -> Result<(AccountId, Option<AccountId>), InkGroupError>;
+
+    #[ink(message)]
+    /// Return the message ids (see `selectors`) with the 4-byte selector of the core messages
+    fn selectors(&self) -> Vec<(u32, [u8; 4])>;
+
+    #[ink(message)]
+    /// Return the number of members in each weight bucket delimited by the strictly ascending
+    /// `boundaries`: `weight < boundaries[0]`, `boundaries[0] <= weight < boundaries[1]`, ..,
+    /// `weight >= boundaries[n - 1]` (`n + 1` buckets)
+    fn weight_buckets(&self, boundaries: Vec<u64>) -> Result<Vec<u32>, InkGroupError>;
+
+    #[ink(message)]
+    /// Return the highest role held by the caller (`Admin > Operator > Member > None`)
+    fn my_role(&self) -> Role;
+
+    #[ink(message)]
+    /// Return if the group can pass a vote, i.e. the total voting power is above zero (false if
+    /// every member has a zero weight)
+    fn is_votable(&self) -> bool;
+
+    #[ink(message)]
+    /// Return the members as merkle leaves sorted by address: each leaf is the 32-byte address
+    /// followed by the 8-byte big-endian weight
+    fn member_leaves(&self) -> Vec<[u8; 40]>;
+
+    #[ink(message)]
+    /// Return the merkle root of `member_leaves` (see the README for the tree construction)
+    fn members_root(&self) -> [u8; 32];
+
+    #[ink(message)]
+    /// Return the minimum number of members, taken from the largest weight down, whose combined
+    /// weight reaches `percent` of the total voting power
+    fn min_coalition_for(&self, percent: u32) -> Result<u32, InkGroupError>;
+
+    #[ink(message)]
+    /// Check a candidate full member list against every validation rule of the group, without
+    /// applying it, and return the first violation
+    fn validate_member_set(&self, members: Vec<Member>) -> Result<(), InkGroupError>;
+
+    #[ink(message)]
+    /// Return a digest of the roster (admin, members, epoch and last mutation) that changes
+    /// whenever the group changes, for members to sign off-chain (see the README for the
+    /// encoding)
+    fn roster_digest(&self) -> Result<[u8; 32], InkGroupError>;
+
+    #[ink(message)]
+    /// Return a page of up to `limit` members after the `cursor` member (from the first if
+    /// `None`) with the cursor of the next page and the current epoch (error `NoMember` if the
+    /// cursor is not in the group)
+    fn sync_checkpoint(
+        &self,
+        cursor: Option<AccountId>,
+        limit: u32,
+    ) -> Result<SyncPage, InkGroupError>;
+
+    #[ink(message)]
+    /// Return the addresses stored more than once (empty unless the storage is corrupted)
+    fn find_duplicates(&self) -> Vec<AccountId>;
+
+    #[ink(message)]
+    /// Return the members added or whose weight changed at or after `epoch` (the removed members
+    /// can't be reported)
+    fn members_changed_since_epoch(&self, epoch: u64) -> Vec<Member>;
+
+    #[ink(message)]
+    /// Return for each member the number of coalitions of the other members it makes reach
+    /// `percent` of the total voting power (raw Banzhaf index, an approximation of the voting
+    /// power). Bounded to small groups
+    fn pivotal_count(&self, percent: u32) -> Result<Vec<(AccountId, u32)>, InkGroupError>;
+
+    #[ink(message)]
+    /// Return the Gini coefficient of the weights in basis points (0 for equal weights, close to
+    /// 10000 when one member holds all the voting power). Bounded to small and medium groups
+    fn weight_gini_bps(&self) -> Result<u32, InkGroupError>;
+
+    #[ink(message)]
+    /// Return the admin, the balance of the contract account and the total voting power
+    fn treasury_view(&self) -> Result<(AccountId, u128, u64), InkGroupError>;
+
+    #[ink(message)]
+    /// Return the number of members and the number of members with a weight above zero
+    fn membership_counts(&self) -> (u32, u32);
+
+    #[ink(message)]
+    /// Return the lowest weight among the members (error `LogicErr` if there are no members)
+    fn min_weight(&self) -> Result<u64, InkGroupError>;
+
+    #[ink(message)]
+    /// Return the highest weight among the members (error `LogicErr` if there are no members)
+    fn max_weight(&self) -> Result<u64, InkGroupError>;
+
+    #[ink(message)]
+    /// Return each account with its weight (`None` if not a member), in the input order
+    fn lookup_weights(
+        &self,
+        accounts: Vec<AccountId>,
+    ) -> Result<Vec<(AccountId, Option<u64>)>, InkGroupError>;
+
+    #[ink(message)]
+    /// Return the status of the group in one call, for monitoring
+    fn health(&self) -> GroupHealth;
+
+    #[ink(message)]
+    /// Check that setting the members to the target weights respects the rules of the group,
+    /// without storing anything
+    fn distribution_feasible(&self, target: Vec<(AccountId, u64)>) -> Result<(), InkGroupError>;
+
+    #[ink(message)]
+    /// Return the weight the member must gain to reach `target_rank` (1-based, 0 if already
+    /// there)
+    fn weight_to_rank(&self, member: AccountId, target_rank: u32) -> Result<u64, InkGroupError>;
+
+    #[ink(message)]
+    /// Return the weight of a member and its share of the total voting power in basis points
+    fn member_weight_and_share(&self, addr: AccountId) -> Result<(u64, u32), InkGroupError>;
+
+    #[ink(message)]
+    /// Return the total voting power and the number of members if the member were removed,
+    /// without removing it
+    fn impact_of_removing(&self, member: AccountId) -> Result<(u64, u32), InkGroupError>;
+
+    #[ink(message)]
+    /// Return the members as CSV rows (`address,weight`) sorted by address
+    fn export_csv(&self) -> Vec<u8>;
+
+    #[ink(message)]
+    /// Return the code hash of the contract
+    fn code_hash(&self) -> Hash;
+
+    #[ink(message)]
+    /// Return the median weight of the members, the lower middle one for an even number of
+    /// members (error `LogicErr` if there are no members)
+    fn median_weight(&self) -> Result<u64, InkGroupError>;
+
+    #[ink(message)]
+    /// Return the `n` members with the highest weight as `(rank, address, weight, bps)`, highest
+    /// first
+    fn leaderboard(&self, n: u32) -> Vec<(u32, AccountId, u64, u32)>;
+
+    #[ink(message)]
+    /// Return how many more members can be added (`None` if the number of members is uncapped)
+    fn remaining_capacity(&self) -> Option<u32>;
+
+    #[ink(message)]
+    /// Return whether both addresses are members of the group
+    fn both_members(&self, a: AccountId, b: AccountId) -> bool;
+
+    #[ink(message)]
+    /// Return the weight of the admin as a member (0 if the admin is not a member)
+    fn admin_weight(&self) -> u64;
+
+    #[ink(message)]
+    /// Return `(tier_min, count, tier_weight)` for every non-empty power of ten weight tier
+    /// (`0`, `1-9`, `10-99`, ..), ascending
+    fn tier_distribution(&self) -> Vec<(u64, u32, u64)>;
+
+    #[ink(message)]
+    /// Return the addresses of the `k` members with the highest weight, ties broken by ascending
+    /// address
+    fn committee(&self, k: u32) -> Vec<AccountId>;
+
+    #[ink(message)]
+    /// Return whether the caller is a member with a weight of at least `min`
+    fn caller_has_weight(&self, min: u64) -> bool;
+
+    #[ink(message)]
+    /// Return a hash of the construction parameters, fixed at construction, for auditors to check
+    /// the deployment (see the README of the implementation for the encoding)
+    fn genesis_hash(&self) -> [u8; 32];
+
+    #[ink(message)]
+    /// Return an estimate of the storage footprint of the group in bytes
+    fn storage_estimate(&self) -> u64;
+
+    #[ink(message)]
+    /// Return whether members can currently be added or removed, regardless of the caller
+    fn membership_mutable(&self) -> bool;
+
+    #[ink(message)]
+    /// Return the member at the median position by weight, the lower middle one for an even
+    /// number of members, ties by address (error `LogicErr` if there are no members)
+    fn median_member(&self) -> Result<AccountId, InkGroupError>;
+
+    #[ink(message)]
+    /// Return whether the combined weight of all the active members reaches `percent` of the
+    /// total voting power, i.e. whether the group can reach quorum at all
+    fn quorum_reachable(&self, percent: u32) -> Result<bool, InkGroupError>;
+
+    #[ink(message)]
+    /// Return whether the caller is the pending admin awaiting to accept the admin role (always
+    /// `false` without a two-step admin transfer)
+    fn am_i_pending_admin(&self) -> bool;
+
     // Setters
     #[ink(message)]
     /// Update the admin
     fn update_admin(&mut self, admin: AccountId) -> Result<(), InkGroupError>;
 
     #[ink(message)]
-    /// Update the members in the group
+    /// Update the members in the group and return the new total voting power (error if both
+    /// lists are empty)
     fn update_members(
         &mut self,
         new_members: Vec<Member>,
         remove_members: Vec<AccountId>,
+    ) -> Result<u64, InkGroupError>;
+
+    #[ink(message)]
+    /// Remove every member with a weight below `threshold` and return how many were removed
+    /// (error if no member would be left)
+    fn prune_below_weight(&mut self, threshold: u64) -> Result<u32, InkGroupError>;
+
+    #[ink(message)]
+    /// Update the weight of an existing member (error `NoMember` if not in the group)
+    fn update_member_weight(&mut self, member: AccountId, weight: u64)
+        -> Result<(), InkGroupError>;
+
+    #[ink(message)]
+    /// Set the weight of an existing member and make it the admin in one step (error `NoMember`
+    /// if not in the group)
+    fn promote_to_controller(
+        &mut self,
+        member: AccountId,
+        weight: u64,
     ) -> Result<(), InkGroupError>;
+
+    #[ink(message)]
+    /// Remove a member from the group (no-op if not in the group, like `update_members`)
+    fn remove_member(&mut self, member: AccountId) -> Result<(), InkGroupError>;
+
+    #[ink(message)]
+    /// Remove the member at `index` in the members list and return it (error `NoMember` if the
+    /// index is out of range). Indexes shift on every removal
+    fn remove_member_at(&mut self, index: u32) -> Result<Member, InkGroupError>;
+
+    #[ink(message)]
+    /// Remove the members from the group, strictly: error `DuplicateMember` if an address is
+    /// entered twice and `NoMember` if an address is not in the group
+    fn remove_members(&mut self, members: Vec<AccountId>) -> Result<(), InkGroupError>;
+
+    #[ink(message)]
+    /// Apply signed deltas to the weight of existing members, all or nothing (error
+    /// `WeightUnderflow` if a weight would go below zero and `NoMember` for an address not in
+    /// the group)
+    fn adjust_weights(&mut self, deltas: Vec<(AccountId, i64)>) -> Result<(), InkGroupError>;
+
+    #[ink(message)]
+    /// Remove the caller from the group (error `NoMember` if not a member), if the implementation
+    /// allows members to leave on their own
+    fn leave(&mut self) -> Result<(), InkGroupError>;
+
+    #[ink(message)]
+    /// Apply the minimal set of additions, updates and removals turning the members into
+    /// `desired`, atomically, and return how many members were touched
+    fn sync_to(&mut self, desired: Vec<Member>) -> Result<UpdateReport, InkGroupError>;
 }
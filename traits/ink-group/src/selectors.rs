@@ -0,0 +1,41 @@
+//! Stable message ids and 4-byte selectors of the core `InkGroup` messages, for thin clients
+//! calling by selector without the contract metadata.
+//!
+//! The selectors are the ones ink! derives for trait messages: the first 4 bytes of the
+//! BLAKE2b-256 hash of `"InkGroup::<message>"`.
+
+/// Message id of `get_admin`
+pub const GET_ADMIN: u32 = 0;
+/// Message id of `get_members`
+pub const GET_MEMBERS: u32 = 1;
+/// Message id of `get_member`
+pub const GET_MEMBER: u32 = 2;
+/// Message id of `get_total_weight`
+pub const GET_TOTAL_WEIGHT: u32 = 3;
+/// Message id of `update_admin`
+pub const UPDATE_ADMIN: u32 = 4;
+/// Message id of `update_members`
+pub const UPDATE_MEMBERS: u32 = 5;
+
+/// Selector of `get_admin`
+pub const GET_ADMIN_SELECTOR: [u8; 4] = ink::selector_bytes!("InkGroup::get_admin");
+/// Selector of `get_members`
+pub const GET_MEMBERS_SELECTOR: [u8; 4] = ink::selector_bytes!("InkGroup::get_members");
+/// Selector of `get_member`
+pub const GET_MEMBER_SELECTOR: [u8; 4] = ink::selector_bytes!("InkGroup::get_member");
+/// Selector of `get_total_weight`
+pub const GET_TOTAL_WEIGHT_SELECTOR: [u8; 4] = ink::selector_bytes!("InkGroup::get_total_weight");
+/// Selector of `update_admin`
+pub const UPDATE_ADMIN_SELECTOR: [u8; 4] = ink::selector_bytes!("InkGroup::update_admin");
+/// Selector of `update_members`
+pub const UPDATE_MEMBERS_SELECTOR: [u8; 4] = ink::selector_bytes!("InkGroup::update_members");
+
+/// Message ids with their selector of the core messages
+pub const CORE: [(u32, [u8; 4]); 6] = [
+    (GET_ADMIN, GET_ADMIN_SELECTOR),
+    (GET_MEMBERS, GET_MEMBERS_SELECTOR),
+    (GET_MEMBER, GET_MEMBER_SELECTOR),
+    (GET_TOTAL_WEIGHT, GET_TOTAL_WEIGHT_SELECTOR),
+    (UPDATE_ADMIN, UPDATE_ADMIN_SELECTOR),
+    (UPDATE_MEMBERS, UPDATE_MEMBERS_SELECTOR),
+];
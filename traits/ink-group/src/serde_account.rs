@@ -0,0 +1,52 @@
+//! (De)serialize an `AccountId` as a `0x` prefixed hex string, to be used with
+//! `#[serde(with = "crate::serde_account")]`.
+
+use ink::prelude::string::String;
+use ink::primitives::AccountId;
+use serde::{de::Error, Deserialize, Deserializer, Serializer};
+
+use crate::display::AccountHex;
+
+pub fn serialize<S: Serializer>(account: &AccountId, serializer: S) -> Result<S::Ok, S::Error> {
+    serializer.collect_str(&AccountHex(account))
+}
+
+pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<AccountId, D::Error> {
+    let hex = String::deserialize(deserializer)?;
+    let hex = hex.strip_prefix("0x").unwrap_or(&hex);
+    if hex.len() != 64 || !hex.is_ascii() {
+        return Err(D::Error::custom("expected a 32 bytes hex address"));
+    }
+    let mut bytes = [0u8; 32];
+    for (index, byte) in bytes.iter_mut().enumerate() {
+        *byte = u8::from_str_radix(&hex[2 * index..2 * index + 2], 16).map_err(D::Error::custom)?;
+    }
+    Ok(AccountId::from(bytes))
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::{InkGroupError, Member};
+    use ink::primitives::AccountId;
+
+    #[test]
+    fn member_round_trip_works() {
+        let member = Member {
+            addr: AccountId::from([0xab; 32]),
+            weight: 7,
+        };
+        let json = serde_json::to_string(&member).unwrap();
+        assert_eq!(
+            json,
+            format!(r#"{{"addr":"0x{}","weight":7}}"#, "ab".repeat(32))
+        );
+        let decoded: Member = serde_json::from_str(&json).unwrap();
+        assert_eq!(decoded, member);
+        let err = InkGroupError::DuplicateMember {
+            member: AccountId::from([0x01; 32]),
+        };
+        let json = serde_json::to_string(&err).unwrap();
+        assert_eq!(serde_json::from_str::<InkGroupError>(&json).unwrap(), err);
+        assert!(serde_json::from_str::<Member>(r#"{"addr":"0x01","weight":7}"#).is_err());
+    }
+}
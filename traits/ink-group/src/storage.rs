@@ -1,13 +1,138 @@
+use ink::prelude::vec::Vec;
 use ink::primitives::AccountId;
 use scale::{Decode, Encode};
 
+use crate::{error::InkGroupError, weight::Weight};
+
 #[derive(Debug, Copy, Clone, PartialEq, Eq, Ord, PartialOrd, Hash, Encode, Decode)]
 #[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 /// Member of the group
-pub struct Member {
+pub struct Member<W: Weight = u64> {
     /// Address of the member
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_account"))]
     pub addr: AccountId,
     /// Voting power of the member (it can be 0, the member will be part of the group but can't
     /// vote)
-    pub weight: u64,
+    pub weight: W,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Ord, PartialOrd, Hash, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+/// Role of an account in the group, ordered from the lowest to the highest
+pub enum Role {
+    /// Not part of the group
+    None,
+    /// Member of the group
+    Member,
+    /// Operator of the group
+    Operator,
+    /// Admin of the group
+    Admin,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+/// Page of members of a resumable sync
+pub struct SyncPage {
+    /// Members of the page, by storage order
+    pub members: Vec<Member>,
+    /// Cursor of the next page (`None` if this is the last page)
+    pub next_cursor: Option<AccountId>,
+    /// Membership epoch when the page was read
+    pub epoch: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+/// Status of the group for monitoring
+pub struct GroupHealth {
+    /// Number of members
+    pub member_count: u32,
+    /// Total voting power
+    pub total_weight: u64,
+    /// Admin of the group (`None` only if the storage is corrupted)
+    pub admin: Option<AccountId>,
+    /// Block number of the last successful mutation
+    pub last_mutated: u32,
+    /// Membership epoch
+    pub epoch: u64,
+}
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Encode, Decode)]
+#[cfg_attr(feature = "std", derive(scale_info::TypeInfo))]
+/// Number of members touched by a synchronization
+pub struct UpdateReport {
+    /// Members added
+    pub added: u32,
+    /// Members whose weight was updated
+    pub updated: u32,
+    /// Members removed
+    pub removed: u32,
+}
+
+/// Compact a member for storage, rejecting a weight above `u32::MAX` with `WeightTooLarge`.
+impl TryFrom<Member> for Member<u32> {
+    type Error = InkGroupError;
+
+    fn try_from(member: Member) -> Result<Self, Self::Error> {
+        let weight = u32::try_from(member.weight).map_err(|_| InkGroupError::WeightTooLarge {})?;
+        Ok(Member {
+            addr: member.addr,
+            weight,
+        })
+    }
+}
+
+/// Widen a compact member, so that totals are always summed as `u64` without overflowing.
+impl From<Member<u32>> for Member {
+    fn from(member: Member<u32>) -> Self {
+        Member {
+            addr: member.addr,
+            weight: u64::from(member.weight),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn compact_member_works() {
+        let addr = AccountId::from([0x01; 32]);
+        let member = Member {
+            addr,
+            weight: u64::from(u32::MAX),
+        };
+        let compact = Member::<u32>::try_from(member).unwrap();
+        assert_eq!(compact.weight, u32::MAX);
+        assert_eq!(compact.encode().len(), 36);
+        let too_large = Member {
+            addr,
+            weight: u64::from(u32::MAX) + 1,
+        };
+        assert_eq!(
+            Member::<u32>::try_from(too_large).unwrap_err(),
+            InkGroupError::WeightTooLarge {}
+        );
+        // The total of compact members is summed as u64
+        let total: u64 = [compact, compact]
+            .into_iter()
+            .map(|member| Member::<u64>::from(member).weight)
+            .sum();
+        assert_eq!(total, 2 * u64::from(u32::MAX));
+    }
+
+    #[test]
+    fn role_order_works() {
+        assert!(Role::Admin > Role::Operator);
+        assert!(Role::Operator > Role::Member);
+        assert!(Role::Member > Role::None);
+        assert_eq!(
+            [Role::Member, Role::Admin, Role::None].iter().max(),
+            Some(&Role::Admin)
+        );
+    }
 }
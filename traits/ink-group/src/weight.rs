@@ -0,0 +1,108 @@
+use scale::{Decode, Encode};
+
+use crate::error::InkGroupError;
+
+mod sealed {
+    pub trait Sealed {}
+
+    impl Sealed for u32 {}
+    impl Sealed for u64 {}
+    impl Sealed for u128 {}
+}
+
+/// Numeric type usable as the voting power of a member.
+///
+/// The trait is sealed: only `u32` (compact storage for small weights), `u64` (simple groups) and
+/// `u128` (token-weighted groups) are supported. The weight type is part of the scale encoding of
+/// a [`Member`](crate::Member): a `u64` weight is encoded in 8 bytes and a `u128` weight in 16
+/// bytes, so `Member<u64>` and `Member<u128>` are not wire compatible and clients must know which
+/// one the group uses (the contract metadata exposes the concrete type).
+///
+/// Note that `#[ink::trait_definition]` doesn't support generic traits, so the `InkGroup` messages
+/// are defined over the default `u64` weight.
+pub trait Weight: sealed::Sealed + Copy + Default + Ord {
+    /// Checked addition, `None` on overflow
+    fn checked_add(self, other: Self) -> Option<Self>;
+    /// Checked subtraction, `None` on underflow
+    fn checked_sub(self, other: Self) -> Option<Self>;
+}
+
+macro_rules! impl_weight {
+    ( $( $ty:ty ),* ) => {
+        $(
+            impl Weight for $ty {
+                fn checked_add(self, other: Self) -> Option<Self> {
+                    <$ty>::checked_add(self, other)
+                }
+
+                fn checked_sub(self, other: Self) -> Option<Self> {
+                    <$ty>::checked_sub(self, other)
+                }
+            }
+        )*
+    };
+}
+
+impl_weight!(u32, u64, u128);
+
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Encode, Decode)]
+#[cfg_attr(
+    feature = "std",
+    derive(scale_info::TypeInfo, ink::storage::traits::StorageLayout)
+)]
+/// Total voting power of a group.
+///
+/// The value can only be changed through checked arithmetic, so a raw `+=` or `-=` that could
+/// overflow or underflow doesn't compile.
+pub struct TotalWeight(u64);
+
+impl TotalWeight {
+    /// Wrap a total computed elsewhere (e.g. a checked sum of the member weights)
+    pub fn new(total: u64) -> Self {
+        TotalWeight(total)
+    }
+
+    /// Return the total as `u64`
+    pub fn get(self) -> u64 {
+        self.0
+    }
+
+    /// Add the weight of a member, failing with `WeightOverflow` on overflow
+    pub fn add_weight(&mut self, weight: u64) -> Result<(), InkGroupError> {
+        self.0 = self
+            .0
+            .checked_add(weight)
+            .ok_or(InkGroupError::WeightOverflow {})?;
+        Ok(())
+    }
+
+    /// Subtract the weight of a member, failing with `LogicErr` on underflow: the total is the
+    /// sum of the member weights, so it can't be below the weight of a member
+    pub fn sub_weight(&mut self, weight: u64) -> Result<(), InkGroupError> {
+        self.0 = self
+            .0
+            .checked_sub(weight)
+            .ok_or(InkGroupError::LogicErr {})?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn total_weight_works() {
+        let mut total = TotalWeight::new(u64::MAX - 1);
+        total.add_weight(1).unwrap();
+        assert_eq!(total.get(), u64::MAX);
+        assert_eq!(total.add_weight(1), Err(InkGroupError::WeightOverflow {}));
+        // A failed operation leaves the total unchanged
+        assert_eq!(total.get(), u64::MAX);
+        let mut total = TotalWeight::default();
+        assert_eq!(total.sub_weight(1), Err(InkGroupError::LogicErr {}));
+        total.add_weight(3).unwrap();
+        total.sub_weight(2).unwrap();
+        assert_eq!(total, TotalWeight::new(1));
+    }
+}